@@ -0,0 +1,372 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{Mat3, Mat4, Mesh, Vec3},
+    render::{
+        mesh::{Indices, MeshVertexAttribute, MeshVertexAttributeId, PrimitiveTopology, VertexAttributeValues},
+        render_resource::VertexFormat,
+    },
+};
+
+pub use self::{
+    bound::*, capture::*, connectivity::*, curve::*, decimate::*, edit::*, fracture::*, heightfield::*, polygonize::*,
+    ramp::*, random::*, remesh::*, spatial::*, spline::*, surface::*, topology::*, triangulate::*, volume::*, weld::*,
+};
+
+pub mod bound;
+pub mod capture;
+pub mod connectivity;
+pub mod curve;
+pub mod decimate;
+pub mod edit;
+pub mod fracture;
+pub mod heightfield;
+pub mod polygonize;
+pub mod ramp;
+pub mod random;
+pub mod remesh;
+pub mod spatial;
+pub mod spline;
+pub mod surface;
+pub mod topology;
+pub mod triangulate;
+pub mod volume;
+pub mod weld;
+
+pub const ATTRIBUTE_COPYNUM: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Copynum", 986_301_001, VertexFormat::Uint32);
+
+/// Connected piece a vertex belongs to, as labelled by `node::Connectivity`.
+pub const ATTRIBUTE_CLASS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Class", 986_301_002, VertexFormat::Uint32);
+
+/// Thickness of a curve at a vertex, as written by `node::LSystem` and followed by `node::Sweep`.
+pub const ATTRIBUTE_WIDTH: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Width", 986_301_003, VertexFormat::Float32);
+
+/// Rewriting generation that created a vertex, as written by `node::LSystem`.
+pub const ATTRIBUTE_GENERATION: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Generation", 986_301_004, VertexFormat::Uint32);
+
+/// Height of a terrain vertex normalized over its heightfield, as written by `node::HeightfieldConvert`.
+pub const ATTRIBUTE_HEIGHT: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Height", 986_301_005, VertexFormat::Float32);
+
+/// Steepness of a terrain vertex normalized over a quarter turn, as written by `node::HeightfieldConvert`.
+pub const ATTRIBUTE_SLOPE: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Slope", 986_301_006, VertexFormat::Float32);
+
+/// Piece a vertex belongs to after `node::VoronoiFracture`, numbered over the whole object.
+pub const ATTRIBUTE_NAME: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Name", 986_301_007, VertexFormat::Uint32);
+
+/// Attributes defined by this crate, which `merge` keeps by padding the meshes that lack them.
+pub const CUSTOM_ATTRIBUTES: [MeshVertexAttribute; 7] = [
+    ATTRIBUTE_COPYNUM,
+    ATTRIBUTE_CLASS,
+    ATTRIBUTE_WIDTH,
+    ATTRIBUTE_GENERATION,
+    ATTRIBUTE_HEIGHT,
+    ATTRIBUTE_SLOPE,
+    ATTRIBUTE_NAME,
+];
+
+/// Values of a custom attribute for vertices that do not have it: a width of 1 and zero for everything else.
+pub fn default_values(attribute: &MeshVertexAttribute, count: usize) -> Option<VertexAttributeValues> {
+    match attribute.format {
+        VertexFormat::Float32 => {
+            let value = if attribute.id == ATTRIBUTE_WIDTH.id { 1.0 } else { 0.0 };
+            Some(VertexAttributeValues::Float32(vec![value; count]))
+        },
+        VertexFormat::Uint32 => Some(VertexAttributeValues::Uint32(vec![0; count])),
+        _ => None,
+    }
+}
+
+macro_rules! map_values {
+    ($values:expr, $vec:ident => $body:expr) => {
+        match $values {
+            VertexAttributeValues::Float32($vec) => VertexAttributeValues::Float32($body),
+            VertexAttributeValues::Sint32($vec) => VertexAttributeValues::Sint32($body),
+            VertexAttributeValues::Uint32($vec) => VertexAttributeValues::Uint32($body),
+            VertexAttributeValues::Float32x2($vec) => VertexAttributeValues::Float32x2($body),
+            VertexAttributeValues::Sint32x2($vec) => VertexAttributeValues::Sint32x2($body),
+            VertexAttributeValues::Uint32x2($vec) => VertexAttributeValues::Uint32x2($body),
+            VertexAttributeValues::Float32x3($vec) => VertexAttributeValues::Float32x3($body),
+            VertexAttributeValues::Sint32x3($vec) => VertexAttributeValues::Sint32x3($body),
+            VertexAttributeValues::Uint32x3($vec) => VertexAttributeValues::Uint32x3($body),
+            VertexAttributeValues::Float32x4($vec) => VertexAttributeValues::Float32x4($body),
+            VertexAttributeValues::Sint32x4($vec) => VertexAttributeValues::Sint32x4($body),
+            VertexAttributeValues::Uint32x4($vec) => VertexAttributeValues::Uint32x4($body),
+            VertexAttributeValues::Sint16x2($vec) => VertexAttributeValues::Sint16x2($body),
+            VertexAttributeValues::Snorm16x2($vec) => VertexAttributeValues::Snorm16x2($body),
+            VertexAttributeValues::Uint16x2($vec) => VertexAttributeValues::Uint16x2($body),
+            VertexAttributeValues::Unorm16x2($vec) => VertexAttributeValues::Unorm16x2($body),
+            VertexAttributeValues::Sint16x4($vec) => VertexAttributeValues::Sint16x4($body),
+            VertexAttributeValues::Snorm16x4($vec) => VertexAttributeValues::Snorm16x4($body),
+            VertexAttributeValues::Uint16x4($vec) => VertexAttributeValues::Uint16x4($body),
+            VertexAttributeValues::Unorm16x4($vec) => VertexAttributeValues::Unorm16x4($body),
+            VertexAttributeValues::Sint8x2($vec) => VertexAttributeValues::Sint8x2($body),
+            VertexAttributeValues::Snorm8x2($vec) => VertexAttributeValues::Snorm8x2($body),
+            VertexAttributeValues::Uint8x2($vec) => VertexAttributeValues::Uint8x2($body),
+            VertexAttributeValues::Unorm8x2($vec) => VertexAttributeValues::Unorm8x2($body),
+            VertexAttributeValues::Sint8x4($vec) => VertexAttributeValues::Sint8x4($body),
+            VertexAttributeValues::Snorm8x4($vec) => VertexAttributeValues::Snorm8x4($body),
+            VertexAttributeValues::Uint8x4($vec) => VertexAttributeValues::Uint8x4($body),
+            VertexAttributeValues::Unorm8x4($vec) => VertexAttributeValues::Unorm8x4($body),
+        }
+    };
+}
+
+macro_rules! zip_values {
+    ($left:expr, $right:expr, $a:ident, $b:ident => $body:expr) => {
+        match ($left, $right) {
+            (VertexAttributeValues::Float32($a), VertexAttributeValues::Float32($b)) => Some($body),
+            (VertexAttributeValues::Sint32($a), VertexAttributeValues::Sint32($b)) => Some($body),
+            (VertexAttributeValues::Uint32($a), VertexAttributeValues::Uint32($b)) => Some($body),
+            (VertexAttributeValues::Float32x2($a), VertexAttributeValues::Float32x2($b)) => Some($body),
+            (VertexAttributeValues::Sint32x2($a), VertexAttributeValues::Sint32x2($b)) => Some($body),
+            (VertexAttributeValues::Uint32x2($a), VertexAttributeValues::Uint32x2($b)) => Some($body),
+            (VertexAttributeValues::Float32x3($a), VertexAttributeValues::Float32x3($b)) => Some($body),
+            (VertexAttributeValues::Sint32x3($a), VertexAttributeValues::Sint32x3($b)) => Some($body),
+            (VertexAttributeValues::Uint32x3($a), VertexAttributeValues::Uint32x3($b)) => Some($body),
+            (VertexAttributeValues::Float32x4($a), VertexAttributeValues::Float32x4($b)) => Some($body),
+            (VertexAttributeValues::Sint32x4($a), VertexAttributeValues::Sint32x4($b)) => Some($body),
+            (VertexAttributeValues::Uint32x4($a), VertexAttributeValues::Uint32x4($b)) => Some($body),
+            (VertexAttributeValues::Sint16x2($a), VertexAttributeValues::Sint16x2($b)) => Some($body),
+            (VertexAttributeValues::Snorm16x2($a), VertexAttributeValues::Snorm16x2($b)) => Some($body),
+            (VertexAttributeValues::Uint16x2($a), VertexAttributeValues::Uint16x2($b)) => Some($body),
+            (VertexAttributeValues::Unorm16x2($a), VertexAttributeValues::Unorm16x2($b)) => Some($body),
+            (VertexAttributeValues::Sint16x4($a), VertexAttributeValues::Sint16x4($b)) => Some($body),
+            (VertexAttributeValues::Snorm16x4($a), VertexAttributeValues::Snorm16x4($b)) => Some($body),
+            (VertexAttributeValues::Uint16x4($a), VertexAttributeValues::Uint16x4($b)) => Some($body),
+            (VertexAttributeValues::Unorm16x4($a), VertexAttributeValues::Unorm16x4($b)) => Some($body),
+            (VertexAttributeValues::Sint8x2($a), VertexAttributeValues::Sint8x2($b)) => Some($body),
+            (VertexAttributeValues::Snorm8x2($a), VertexAttributeValues::Snorm8x2($b)) => Some($body),
+            (VertexAttributeValues::Uint8x2($a), VertexAttributeValues::Uint8x2($b)) => Some($body),
+            (VertexAttributeValues::Unorm8x2($a), VertexAttributeValues::Unorm8x2($b)) => Some($body),
+            (VertexAttributeValues::Sint8x4($a), VertexAttributeValues::Sint8x4($b)) => Some($body),
+            (VertexAttributeValues::Snorm8x4($a), VertexAttributeValues::Snorm8x4($b)) => Some($body),
+            (VertexAttributeValues::Uint8x4($a), VertexAttributeValues::Uint8x4($b)) => Some($body),
+            (VertexAttributeValues::Unorm8x4($a), VertexAttributeValues::Unorm8x4($b)) => Some($body),
+            _ => None,
+        }
+    };
+}
+
+pub fn positions(mesh: &Mesh) -> Vec<Vec3> {
+    match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions.iter().copied().map(Vec3::from).collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn set_positions(mesh: &mut Mesh, positions: &[Vec3]) {
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        positions.iter().map(|position| position.to_array()).collect::<Vec<_>>(),
+    );
+}
+
+pub fn normals(mesh: &Mesh) -> Option<Vec<Vec3>> {
+    match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(normals)) => Some(normals.iter().copied().map(Vec3::from).collect()),
+        _ => None,
+    }
+}
+
+/// Normals averaged over vertices sharing a position, so that split vertices (hard edges, UV seams) move
+/// together when displaced along them.
+pub fn point_normals(mesh: &Mesh) -> Option<Vec<Vec3>> {
+    let positions = positions(mesh);
+    let normals = normals(mesh)?;
+
+    let mut shared = HashMap::<[u32; 3], Vec3>::new();
+    for (position, normal) in positions.iter().zip(&normals) {
+        *shared.entry(position.to_array().map(f32::to_bits)).or_default() += *normal;
+    }
+
+    Some(
+        positions
+            .iter()
+            .map(|position| shared[&position.to_array().map(f32::to_bits)].normalize_or_zero())
+            .collect(),
+    )
+}
+
+/// Reads a scalar attribute as floats, converting from the integer formats.
+pub fn scalars(mesh: &Mesh, id: impl Into<MeshVertexAttributeId>) -> Option<Vec<f32>> {
+    match mesh.attribute(id)? {
+        VertexAttributeValues::Float32(values) => Some(values.clone()),
+        VertexAttributeValues::Uint32(values) => Some(values.iter().map(|&value| value as f32).collect()),
+        VertexAttributeValues::Sint32(values) => Some(values.iter().map(|&value| value as f32).collect()),
+        _ => None,
+    }
+}
+
+/// Returns the vertex indices of the mesh, generating sequential ones for non-indexed meshes.
+pub fn indices(mesh: &Mesh) -> Vec<u32> {
+    match mesh.indices() {
+        Some(indices) => indices.iter().map(|idx| idx as u32).collect(),
+        None => (0..mesh.count_vertices() as u32).collect(),
+    }
+}
+
+pub fn set_indices(mesh: &mut Mesh, indices: Vec<u32>) {
+    mesh.set_indices(Some(Indices::U32(indices)));
+}
+
+pub fn triangles(mesh: &Mesh) -> Vec<[u32; 3]> {
+    let indices = indices(mesh);
+    match mesh.primitive_topology() {
+        PrimitiveTopology::TriangleList => indices.chunks_exact(3).map(|tri| [tri[0], tri[1], tri[2]]).collect(),
+        PrimitiveTopology::TriangleStrip => indices
+            .windows(3)
+            .enumerate()
+            .map(|(idx, tri)| {
+                if idx % 2 == 0 {
+                    [tri[0], tri[1], tri[2]]
+                } else {
+                    [tri[1], tri[0], tri[2]]
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Recomputes vertex normals as the area-weighted average of the adjacent triangle normals.
+pub fn compute_normals(mesh: &mut Mesh) {
+    let triangles = triangles(mesh);
+    if triangles.is_empty() {
+        return;
+    }
+
+    let positions = positions(mesh);
+    let mut normals = vec![Vec3::ZERO; positions.len()];
+    for tri in triangles {
+        let [a, b, c] = tri.map(|idx| idx as usize);
+        let normal = (positions[b] - positions[a]).cross(positions[c] - positions[a]);
+        normals[a] += normal;
+        normals[b] += normal;
+        normals[c] += normal;
+    }
+
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        normals
+            .into_iter()
+            .map(|normal| normal.normalize_or_zero().to_array())
+            .collect::<Vec<_>>(),
+    );
+}
+
+pub fn triangle_area(a: Vec3, b: Vec3, c: Vec3) -> f32 {
+    (b - a).cross(c - a).length() * 0.5
+}
+
+pub fn triangle_normal(a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    (b - a).cross(c - a).normalize_or_zero()
+}
+
+pub fn gather_values(values: &VertexAttributeValues, vertices: &[u32]) -> VertexAttributeValues {
+    map_values!(values, vec => vertices.iter().map(|&idx| vec[idx as usize]).collect())
+}
+
+pub fn extend_values(values: &mut VertexAttributeValues, other: &VertexAttributeValues) -> bool {
+    zip_values!(values, other, vec, other_vec => vec.extend_from_slice(other_vec)).is_some()
+}
+
+/// Builds a mesh with the same attributes as `mesh` whose vertices are taken from `vertices` in order.
+pub fn gather(mesh: &Mesh, vertices: &[u32]) -> Mesh {
+    let mut result = mesh.clone();
+    for (_, values) in result.attributes_mut() {
+        *values = gather_values(values, vertices);
+    }
+    result.set_indices(None);
+    result
+}
+
+pub fn transform(mesh: &mut Mesh, matrix: Mat4) {
+    let normal_matrix = Mat3::from_mat4(matrix).inverse().transpose();
+
+    if let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute_mut(Mesh::ATTRIBUTE_POSITION) {
+        for position in positions.iter_mut() {
+            *position = matrix.transform_point3(Vec3::from(*position)).to_array();
+        }
+    }
+
+    if let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL) {
+        for normal in normals.iter_mut() {
+            *normal = (normal_matrix * Vec3::from(*normal)).normalize_or_zero().to_array();
+        }
+    }
+
+    if let Some(VertexAttributeValues::Float32x4(tangents)) = mesh.attribute_mut(Mesh::ATTRIBUTE_TANGENT) {
+        for tangent in tangents.iter_mut() {
+            let direction = matrix
+                .transform_vector3(Vec3::new(tangent[0], tangent[1], tangent[2]))
+                .normalize_or_zero();
+            *tangent = [direction.x, direction.y, direction.z, tangent[3]];
+        }
+    }
+}
+
+/// Concatenates meshes of the same list topology into one, keeping the attributes all of them share. Custom
+/// attributes are kept as long as any mesh has them, with [`default_values`] for the vertices of the others.
+pub fn merge(meshes: &[Mesh]) -> Option<Mesh> {
+    let (first, rest) = meshes.split_first()?;
+    let topology = first.primitive_topology();
+    if matches!(
+        topology,
+        PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip
+    ) || rest.iter().any(|mesh| mesh.primitive_topology() != topology)
+    {
+        return None;
+    }
+
+    let mut result = first.clone();
+    let mut merged_indices = indices(first);
+
+    for attribute in &CUSTOM_ATTRIBUTES {
+        if !result.contains_attribute(attribute.id) && rest.iter().any(|mesh| mesh.contains_attribute(attribute.id)) {
+            if let Some(values) = default_values(attribute, first.count_vertices()) {
+                result.insert_attribute(attribute.clone(), values);
+            }
+        }
+    }
+    let custom = |id| CUSTOM_ATTRIBUTES.iter().find(|attribute| attribute.id == id);
+
+    let missing = result
+        .attributes()
+        .filter(|(id, _)| custom(*id).is_none() && rest.iter().any(|mesh| !mesh.contains_attribute(*id)))
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    for id in missing {
+        result.remove_attribute(id);
+    }
+
+    for mesh in rest {
+        let offset = result.count_vertices() as u32;
+        for (id, values) in result.attributes_mut() {
+            match mesh.attribute(id) {
+                Some(other) => {
+                    extend_values(values, other);
+                },
+                None => {
+                    if let Some(padding) =
+                        custom(id).and_then(|attribute| default_values(attribute, mesh.count_vertices()))
+                    {
+                        extend_values(values, &padding);
+                    }
+                },
+            }
+        }
+        merged_indices.extend(indices(mesh).into_iter().map(|idx| idx + offset));
+    }
+
+    if !matches!(topology, PrimitiveTopology::PointList) {
+        set_indices(&mut result, merged_indices);
+    }
+    Some(result)
+}
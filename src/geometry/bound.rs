@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+
+use bevy::{
+    math::DVec3,
+    prelude::{Mat3, Vec3},
+};
+
+/// Axis-aligned bounds of the points as their minimum and maximum corners.
+pub fn aabb(points: &[Vec3]) -> (Vec3, Vec3) {
+    points
+        .iter()
+        .fold((Vec3::splat(f32::MAX), Vec3::splat(f32::MIN)), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        })
+}
+
+/// Box aligned with the principal axes of the points. Returns its center, its axes as the columns of a rotation
+/// matrix and its half extents along them.
+pub fn oriented_box(points: &[Vec3]) -> (Vec3, Mat3, Vec3) {
+    let mean = points.iter().fold(Vec3::ZERO, |sum, point| sum + *point) / points.len().max(1) as f32;
+    let mut covariance = [[0.0; 3]; 3];
+    for point in points {
+        let offset = (*point - mean).to_array();
+        for (row, covariance_row) in covariance.iter_mut().enumerate() {
+            for (column, value) in covariance_row.iter_mut().enumerate() {
+                *value += offset[row] * offset[column];
+            }
+        }
+    }
+
+    let mut axes = symmetric_eigenvectors(covariance);
+    // keep the frame right-handed so that it is a proper rotation
+    axes.z_axis = axes.x_axis.cross(axes.y_axis);
+
+    let local = points.iter().map(|point| axes.transpose() * *point).collect::<Vec<_>>();
+    let (min, max) = aabb(&local);
+    (axes * ((min + max) * 0.5), axes, (max - min) * 0.5)
+}
+
+/// Eigenvectors of a symmetric 3x3 matrix by cyclic Jacobi rotations, as the columns of an orthonormal matrix.
+fn symmetric_eigenvectors(mut matrix: [[f32; 3]; 3]) -> Mat3 {
+    let mut vectors = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+    for _ in 0..32 {
+        let off_diagonal = matrix[0][1].abs() + matrix[0][2].abs() + matrix[1][2].abs();
+        if off_diagonal < 1e-9 {
+            break;
+        }
+
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            if matrix[p][q].abs() < 1e-12 {
+                continue;
+            }
+
+            let theta = (matrix[q][q] - matrix[p][p]) / (2.0 * matrix[p][q]);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+
+            for row in matrix.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+            let (row_p, row_q) = (matrix[p], matrix[q]);
+            matrix[p] = [0, 1, 2].map(|k| c * row_p[k] - s * row_q[k]);
+            matrix[q] = [0, 1, 2].map(|k| s * row_p[k] + c * row_q[k]);
+            for row in vectors.iter_mut() {
+                let (kp, kq) = (row[p], row[q]);
+                row[p] = c * kp - s * kq;
+                row[q] = s * kp + c * kq;
+            }
+        }
+    }
+
+    Mat3::from_cols_array_2d(&vectors).transpose()
+}
+
+/// Bounding sphere after Ritter: a sphere through two far apart points grown to take in every point outside it.
+/// Returns the center and radius; the sphere is at most a few percent larger than the minimal one.
+pub fn bounding_sphere(points: &[Vec3]) -> (Vec3, f32) {
+    let first = match points.first() {
+        Some(first) => *first,
+        None => return (Vec3::ZERO, 0.0),
+    };
+    let farthest = |from: Vec3| {
+        points
+            .iter()
+            .copied()
+            .max_by(|a, b| a.distance_squared(from).total_cmp(&b.distance_squared(from)))
+            .unwrap()
+    };
+
+    let a = farthest(first);
+    let b = farthest(a);
+    let mut center = (a + b) * 0.5;
+    let mut radius = a.distance(b) * 0.5;
+    for point in points {
+        let distance = point.distance(center);
+        if distance > radius {
+            radius = (radius + distance) * 0.5;
+            center += (*point - center) * ((distance - radius) / distance);
+        }
+    }
+    (center, radius)
+}
+
+struct HullFace {
+    corners: [u32; 3],
+    normal: DVec3,
+    offset: f64,
+}
+
+impl HullFace {
+    fn new(points: &[DVec3], corners: [u32; 3]) -> Self {
+        let [a, b, c] = corners.map(|idx| points[idx as usize]);
+        let normal = (b - a).cross(c - a).normalize_or_zero();
+        Self {
+            corners,
+            normal,
+            offset: normal.dot(a),
+        }
+    }
+
+    fn distance(&self, point: DVec3) -> f64 {
+        self.normal.dot(point) - self.offset
+    }
+}
+
+/// Convex hull of the points by incremental construction. Returns outward wound triangles indexing `points`, or
+/// nothing when the points are flat and enclose no volume.
+pub fn convex_hull(points: &[Vec3]) -> Vec<[u32; 3]> {
+    let points = points.iter().map(|point| point.as_dvec3()).collect::<Vec<_>>();
+    let simplex = match initial_simplex(&points) {
+        Some(simplex) => simplex,
+        None => return Vec::new(),
+    };
+
+    let (min, max) = points
+        .iter()
+        .fold((DVec3::splat(f64::MAX), DVec3::splat(f64::MIN)), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        });
+    // input comes in single precision, so anything closer than its rounding error counts as on the face
+    let epsilon = (max - min).max_element() * 1e-5;
+
+    let [a, b, c, d] = simplex;
+    let mut faces = [[a, b, c], [a, c, d], [a, d, b], [b, d, c]]
+        .into_iter()
+        .map(|corners| HullFace::new(&points, corners))
+        .collect::<Vec<_>>();
+    // the simplex faces are wound consistently; flip them all if they point inwards
+    if faces[0].distance(points[d as usize]) > 0.0 {
+        faces = faces
+            .into_iter()
+            .map(|face| {
+                let [a, b, c] = face.corners;
+                HullFace::new(&points, [a, c, b])
+            })
+            .collect();
+    }
+
+    for (idx, point) in points.iter().enumerate() {
+        if simplex.contains(&(idx as u32)) {
+            continue;
+        }
+
+        let (visible, kept): (Vec<_>, Vec<_>) = faces.into_iter().partition(|face| face.distance(*point) > epsilon);
+        faces = kept;
+        if visible.is_empty() {
+            continue;
+        }
+
+        let visible_edges = visible
+            .iter()
+            .flat_map(|face| {
+                let [a, b, c] = face.corners;
+                [(a, b), (b, c), (c, a)]
+            })
+            .collect::<HashSet<_>>();
+        for &(from, to) in &visible_edges {
+            if !visible_edges.contains(&(to, from)) {
+                faces.push(HullFace::new(&points, [from, to, idx as u32]));
+            }
+        }
+    }
+
+    faces.into_iter().map(|face| face.corners).collect()
+}
+
+/// Four points spanning a tetrahedron of non-zero volume, if the points are not flat.
+fn initial_simplex(points: &[DVec3]) -> Option<[u32; 4]> {
+    let farthest_by = |measure: &dyn Fn(DVec3) -> f64| {
+        (0..points.len() as u32).max_by(|&x, &y| measure(points[x as usize]).total_cmp(&measure(points[y as usize])))
+    };
+
+    let a = farthest_by(&|point| point.x)?;
+    let pa = points[a as usize];
+    let b = farthest_by(&|point| point.distance_squared(pa))?;
+    let pb = points[b as usize];
+    let direction = (pb - pa).normalize_or_zero();
+    let c = farthest_by(&|point| (point - pa).cross(direction).length_squared())?;
+    let pc = points[c as usize];
+    let normal = (pb - pa).cross(pc - pa).normalize_or_zero();
+    let d = farthest_by(&|point| normal.dot(point - pa).abs())?;
+
+    let scale = pa.distance(pb);
+    if normal == DVec3::ZERO || normal.dot(points[d as usize] - pa).abs() <= scale * 1e-6 {
+        return None;
+    }
+    Some([a, b, c, d])
+}
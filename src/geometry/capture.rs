@@ -0,0 +1,67 @@
+use bevy::prelude::Vec3;
+
+use crate::{geometry, ProcessObject};
+
+/// Region along an axis that a deformer acts on: from `origin` for `length` units in `direction`.
+#[derive(Copy, Clone, Debug)]
+pub struct Capture {
+    pub origin: Vec3,
+    pub direction: Vec3,
+    pub length: f32,
+}
+
+impl Capture {
+    pub fn new(origin: Vec3, direction: Vec3, length: f32) -> Self {
+        Self {
+            origin,
+            direction,
+            length,
+        }
+    }
+
+    pub fn axis(&self) -> Vec3 {
+        self.direction.normalize_or_zero()
+    }
+
+    /// Distance of `position` along the capture axis, measured from the origin.
+    pub fn along(&self, position: Vec3) -> f32 {
+        (position - self.origin).dot(self.axis())
+    }
+
+    /// Fraction of the capture length reached by `position`, clamped to `[0, 1]`.
+    pub fn ratio(&self, position: Vec3) -> f32 {
+        if self.length <= 0.0 {
+            return 0.0;
+        }
+        (self.along(position) / self.length).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for Capture {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, Vec3::Y, 1.0)
+    }
+}
+
+/// Moves the vertices of every mesh through `deform` and recomputes the normals. With a `group`, only the vertices in
+/// that selection group move, and meshes without any of them are left alone.
+pub fn deform_selected(object: &mut ProcessObject, group: Option<&str>, deform: impl Fn(Vec3) -> Vec3) {
+    for idx in 0..object.meshes.len() {
+        let selected = group.map(|group| object.selected_vertices(group, idx));
+        if matches!(&selected, Some(selected) if selected.is_empty()) {
+            continue;
+        }
+
+        let mesh = &mut object.meshes[idx];
+        let positions = geometry::positions(mesh)
+            .into_iter()
+            .enumerate()
+            .map(|(vertex, position)| match &selected {
+                Some(selected) if !selected.contains(&(vertex as u32)) => position,
+                _ => deform(position),
+            })
+            .collect::<Vec<_>>();
+        geometry::set_positions(mesh, &positions);
+        geometry::compute_normals(mesh);
+    }
+}
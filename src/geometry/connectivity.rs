@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::Mesh,
+    render::mesh::{MeshVertexAttributeId, PrimitiveTopology},
+};
+
+use crate::geometry;
+
+/// Vertex indices of every primitive of the mesh: triangles, line segments or single points.
+pub fn primitives(mesh: &Mesh) -> Vec<Vec<u32>> {
+    let indices = geometry::indices(mesh);
+    match mesh.primitive_topology() {
+        PrimitiveTopology::TriangleList | PrimitiveTopology::TriangleStrip => {
+            geometry::triangles(mesh).into_iter().map(|tri| tri.to_vec()).collect()
+        },
+        PrimitiveTopology::LineList => indices.chunks_exact(2).map(<[u32]>::to_vec).collect(),
+        PrimitiveTopology::LineStrip => indices.windows(2).map(<[u32]>::to_vec).collect(),
+        PrimitiveTopology::PointList => indices.into_iter().map(|idx| vec![idx]).collect(),
+    }
+}
+
+/// Connected pieces of a mesh: vertices belong to the same piece when a primitive joins them or they share a
+/// position. Returns the piece of every vertex, numbered in order of first appearance, and the number of pieces.
+pub fn connected_pieces(mesh: &Mesh) -> (Vec<u32>, u32) {
+    let positions = geometry::positions(mesh);
+    let mut parents = (0..positions.len() as u32).collect::<Vec<_>>();
+
+    let mut first_at = HashMap::new();
+    for (idx, position) in positions.iter().enumerate() {
+        let first = *first_at
+            .entry(position.to_array().map(f32::to_bits))
+            .or_insert(idx as u32);
+        union(&mut parents, first, idx as u32);
+    }
+    for primitive in primitives(mesh) {
+        for pair in primitive.windows(2) {
+            union(&mut parents, pair[0], pair[1]);
+        }
+    }
+
+    let mut numbers = HashMap::new();
+    let pieces = (0..positions.len() as u32)
+        .map(|idx| {
+            let root = find(&mut parents, idx);
+            let next = numbers.len() as u32;
+            *numbers.entry(root).or_insert(next)
+        })
+        .collect();
+    (pieces, numbers.len() as u32)
+}
+
+fn find(parents: &mut [u32], mut idx: u32) -> u32 {
+    while parents[idx as usize] != idx {
+        let grandparent = parents[parents[idx as usize] as usize];
+        parents[idx as usize] = grandparent;
+        idx = grandparent;
+    }
+    idx
+}
+
+fn union(parents: &mut [u32], a: u32, b: u32) {
+    let (a, b) = (find(parents, a), find(parents, b));
+    if a != b {
+        parents[a.max(b) as usize] = a.min(b);
+    }
+}
+
+/// Splits a mesh into one mesh per label, where `labels` gives a label below `count` for every vertex. Primitives go
+/// to the piece of their first vertex and bring along the vertices they use. Returns every piece with the original
+/// indices of its vertices; strip topologies cannot be split and come back whole.
+pub fn split_pieces(mesh: &Mesh, labels: &[u32], count: u32) -> Vec<(Mesh, Vec<u32>)> {
+    let topology = mesh.primitive_topology();
+    if matches!(
+        topology,
+        PrimitiveTopology::LineStrip | PrimitiveTopology::TriangleStrip
+    ) || count <= 1
+    {
+        return vec![(mesh.clone(), (0..mesh.count_vertices() as u32).collect())];
+    }
+
+    let mut piece_primitives = vec![Vec::new(); count as usize];
+    for primitive in primitives(mesh) {
+        piece_primitives[labels[primitive[0] as usize] as usize].push(primitive);
+    }
+
+    piece_primitives
+        .into_iter()
+        .enumerate()
+        .map(|(piece, primitives)| {
+            let mut remap = HashMap::new();
+            let mut vertices = Vec::new();
+            let mut add = |vertex: u32| {
+                *remap.entry(vertex).or_insert_with(|| {
+                    vertices.push(vertex);
+                    vertices.len() as u32 - 1
+                })
+            };
+
+            for (vertex, &label) in labels.iter().enumerate() {
+                if label == piece as u32 {
+                    add(vertex as u32);
+                }
+            }
+            let indices = primitives.into_iter().flatten().map(&mut add).collect::<Vec<_>>();
+
+            let mut result = geometry::gather(mesh, &vertices);
+            if !matches!(topology, PrimitiveTopology::PointList) {
+                geometry::set_indices(&mut result, indices);
+            }
+            (result, vertices)
+        })
+        .collect()
+}
+
+/// Dense labels from the distinct values of a scalar attribute, numbered in order of first appearance. Returns the
+/// label of every vertex and the number of labels.
+pub fn attribute_labels(mesh: &Mesh, id: impl Into<MeshVertexAttributeId>) -> Option<(Vec<u32>, u32)> {
+    let values = geometry::scalars(mesh, id)?;
+    let mut numbers = HashMap::new();
+    let labels = values
+        .into_iter()
+        .map(|value| {
+            let next = numbers.len() as u32;
+            *numbers.entry(value.to_bits()).or_insert(next)
+        })
+        .collect();
+    Some((labels, numbers.len() as u32))
+}
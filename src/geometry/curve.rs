@@ -0,0 +1,242 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    prelude::{Mesh, Quat, Vec3},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::geometry;
+
+/// Polyline through mesh vertices. Curves are stored as `LineList` meshes whose segments chain from one vertex to
+/// the next, with a last segment back to the first vertex when closed.
+#[derive(Clone, Debug, Default)]
+pub struct Curve {
+    pub vertices: Vec<u32>,
+    pub closed: bool,
+}
+
+impl Curve {
+    pub fn points(&self, mesh: &Mesh) -> Vec<Vec3> {
+        let positions = geometry::positions(mesh);
+        self.vertices.iter().map(|&idx| positions[idx as usize]).collect()
+    }
+}
+
+/// Follows the segments of a line mesh into curves, in order of their first segment.
+pub fn curves(mesh: &Mesh) -> Vec<Curve> {
+    let indices = geometry::indices(mesh);
+    let segments = match mesh.primitive_topology() {
+        PrimitiveTopology::LineList => indices
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[1]))
+            .collect::<Vec<_>>(),
+        PrimitiveTopology::LineStrip => indices.windows(2).map(|pair| (pair[0], pair[1])).collect(),
+        _ => return Vec::new(),
+    };
+
+    let mut next = HashMap::new();
+    let mut incoming = HashSet::new();
+    for &(from, to) in &segments {
+        next.entry(from).or_insert(to);
+        incoming.insert(to);
+    }
+
+    // open curves start where no segment arrives; whatever is left afterwards are loops
+    let starts = segments
+        .iter()
+        .map(|(from, _)| *from)
+        .filter(|from| !incoming.contains(from))
+        .chain(segments.iter().map(|(from, _)| *from))
+        .collect::<Vec<_>>();
+
+    let mut visited = HashSet::new();
+    let mut curves = Vec::new();
+    for start in starts {
+        if visited.contains(&start) {
+            continue;
+        }
+
+        let mut curve = Curve {
+            vertices: vec![start],
+            closed: false,
+        };
+        visited.insert(start);
+        let mut current = start;
+        while let Some(&to) = next.get(&current) {
+            if to == start {
+                curve.closed = true;
+                break;
+            }
+            if !visited.insert(to) {
+                break;
+            }
+            curve.vertices.push(to);
+            current = to;
+        }
+        curves.push(curve);
+    }
+
+    // open curves were followed first, so put everything back in the order the segments come in
+    let mut first_segment = HashMap::new();
+    for (idx, &(from, _)) in segments.iter().enumerate() {
+        first_segment.entry(from).or_insert(idx);
+    }
+    curves.sort_by_key(|curve| {
+        curve
+            .vertices
+            .iter()
+            .filter_map(|vertex| first_segment.get(vertex))
+            .min()
+            .copied()
+    });
+    curves
+}
+
+/// Sets UVs on a line mesh with U running from 0 to 1 along every curve by arc length, which the mesh pipeline
+/// needs before it draws the lines.
+pub fn insert_curve_uvs(mesh: &mut Mesh) {
+    let mut uvs = vec![[0.0, 0.0]; mesh.count_vertices()];
+    for curve in curves(mesh) {
+        let lengths = arc_lengths(&curve.points(mesh), curve.closed);
+        let total = lengths.last().copied().unwrap_or(0.0).max(f32::EPSILON);
+        for (&vertex, length) in curve.vertices.iter().zip(lengths) {
+            uvs[vertex as usize] = [length / total, 0.0];
+        }
+    }
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+}
+
+/// Line mesh for a polyline. Lines still go through the lit pipeline, so they get normals facing up and UVs along
+/// their length.
+pub fn polyline(points: &[Vec3], closed: bool) -> Mesh {
+    let count = points.len() as u32;
+    let segment_count = match count {
+        0 | 1 => 0,
+        _ if closed => count,
+        _ => count - 1,
+    };
+
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        points.iter().map(|point| point.to_array()).collect::<Vec<_>>(),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; points.len()]);
+    geometry::set_indices(
+        &mut mesh,
+        (0..segment_count).flat_map(|idx| [idx, (idx + 1) % count]).collect(),
+    );
+    insert_curve_uvs(&mut mesh);
+    mesh
+}
+
+/// Distance along the polyline at every point, followed by the length of the closing segment for closed curves.
+pub fn arc_lengths(points: &[Vec3], closed: bool) -> Vec<f32> {
+    let mut lengths = Vec::with_capacity(points.len() + 1);
+    let mut total = 0.0;
+    for (idx, point) in points.iter().enumerate() {
+        if idx > 0 {
+            total += point.distance(points[idx - 1]);
+        }
+        lengths.push(total);
+    }
+    if closed {
+        if let (Some(first), Some(last)) = (points.first(), points.last()) {
+            lengths.push(total + last.distance(*first));
+        }
+    }
+    lengths
+}
+
+/// Unit tangents of a polyline from the neighbouring points, one-sided at the ends of open curves.
+pub fn tangents(points: &[Vec3], closed: bool) -> Vec<Vec3> {
+    let count = points.len();
+    (0..count)
+        .map(|idx| {
+            let (prev, next) = if closed {
+                (points[(idx + count - 1) % count], points[(idx + 1) % count])
+            } else {
+                (points[idx.saturating_sub(1)], points[(idx + 1).min(count - 1)])
+            };
+            (next - prev).normalize_or_zero()
+        })
+        .collect()
+}
+
+/// Rotation minimizing frames along a polyline by the double reflection method of Wang et al., as a tangent and a
+/// normal per point. On closed curves the twist left over after going around is spread along the curve so that the
+/// last frame lines up with the first.
+pub fn rotation_minimizing_frames(points: &[Vec3], closed: bool) -> Vec<(Vec3, Vec3)> {
+    let tangents = tangents(points, closed);
+    let first_tangent = match tangents.first() {
+        Some(tangent) => *tangent,
+        None => return Vec::new(),
+    };
+    let reference = if first_tangent.y.abs() < 0.9 { Vec3::Y } else { Vec3::X };
+    let mut normals = vec![first_tangent.cross(reference).cross(first_tangent).normalize_or_zero()];
+
+    let reflect = |vector: Vec3, axis: Vec3| {
+        let length_squared = axis.length_squared();
+        if length_squared < f32::EPSILON {
+            vector
+        } else {
+            vector - axis * (2.0 * axis.dot(vector) / length_squared)
+        }
+    };
+    let propagate = |normal: Vec3, from: usize, to: usize| {
+        let step = points[to] - points[from];
+        let reflected_normal = reflect(normal, step);
+        let reflected_tangent = reflect(tangents[from], step);
+        reflect(reflected_normal, tangents[to] - reflected_tangent).normalize_or_zero()
+    };
+
+    for idx in 1..points.len() {
+        let normal = propagate(normals[idx - 1], idx - 1, idx);
+        normals.push(normal);
+    }
+
+    if closed && points.len() > 2 {
+        let around = propagate(normals[points.len() - 1], points.len() - 1, 0);
+        let angle = around
+            .cross(normals[0])
+            .dot(first_tangent)
+            .atan2(around.dot(normals[0]));
+        let lengths = arc_lengths(points, true);
+        let total = lengths.last().copied().unwrap_or(0.0).max(f32::EPSILON);
+        for (idx, normal) in normals.iter_mut().enumerate() {
+            let rotation = Quat::from_axis_angle(tangents[idx], angle * lengths[idx] / total);
+            *normal = rotation * *normal;
+        }
+    }
+
+    tangents.into_iter().zip(normals).collect()
+}
+
+/// Points spaced evenly by arc length along a polyline. Open curves keep both end points; closed curves spread
+/// `count` points around the loop starting at the first point.
+pub fn resample(points: &[Vec3], closed: bool, count: usize) -> Vec<Vec3> {
+    if points.len() < 2 || count < 2 {
+        return points.iter().take(count).copied().collect();
+    }
+
+    let lengths = arc_lengths(points, closed);
+    let total = lengths.last().copied().unwrap_or(0.0);
+    let steps = if closed { count } else { count - 1 };
+
+    let mut segment = 0;
+    (0..count)
+        .map(|idx| {
+            let target = total * idx as f32 / steps as f32;
+            while segment + 2 < lengths.len() && lengths[segment + 1] < target {
+                segment += 1;
+            }
+            let (start, end) = (lengths[segment], lengths[segment + 1]);
+            let ratio = if end > start {
+                (target - start) / (end - start)
+            } else {
+                0.0
+            };
+            points[segment].lerp(points[(segment + 1) % points.len()], ratio.clamp(0.0, 1.0))
+        })
+        .collect()
+}
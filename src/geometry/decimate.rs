@@ -0,0 +1,248 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    ops::{Add, AddAssign},
+};
+
+use bevy::{
+    math::DVec3,
+    prelude::{Mesh, Vec3},
+};
+
+use crate::geometry::{self, EditMesh, Topology};
+
+/// Symmetric 4x4 error quadric of Garland and Heckbert, stored as its upper triangle.
+#[derive(Copy, Clone, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    fn from_plane(normal: DVec3, distance: f64) -> Self {
+        let DVec3 { x: a, y: b, z: c } = normal;
+        let d = distance;
+        Self([a * a, a * b, a * c, a * d, b * b, b * c, b * d, c * c, c * d, d * d])
+    }
+
+    fn error(&self, point: DVec3) -> f64 {
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, dd] = self.0;
+        let DVec3 { x, y, z } = point;
+        let error = aa * x * x
+            + bb * y * y
+            + cc * z * z
+            + dd
+            + 2.0 * (ab * x * y + ac * x * z + ad * x + bc * y * z + bd * y + cd * z);
+        // clamping through a comparison rather than `max` never yields -0.0, whose bits would sort last in the heap
+        if error > 0.0 {
+            error
+        } else {
+            0.0
+        }
+    }
+
+    /// Point minimising the error, if the quadric is well conditioned.
+    fn optimum(&self) -> Option<DVec3> {
+        let [aa, ab, ac, ad, bb, bc, bd, cc, cd, _] = self.0;
+        let det = aa * (bb * cc - bc * bc) - ab * (ab * cc - bc * ac) + ac * (ab * bc - bb * ac);
+        if det.abs() < 1e-12 {
+            return None;
+        }
+
+        let [rx, ry, rz] = [-ad, -bd, -cd];
+        let x = (rx * (bb * cc - bc * bc) - ab * (ry * cc - bc * rz) + ac * (ry * bc - bb * rz)) / det;
+        let y = (aa * (ry * cc - bc * rz) - rx * (ab * cc - bc * ac) + ac * (ab * rz - ry * ac)) / det;
+        let z = (aa * (bb * rz - ry * bc) - ab * (ab * rz - ry * ac) + rx * (ab * bc - bb * ac)) / det;
+        Some(DVec3::new(x, y, z))
+    }
+}
+
+impl Add for Quadric {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl AddAssign for Quadric {
+    fn add_assign(&mut self, other: Self) {
+        for (value, other) in self.0.iter_mut().zip(other.0) {
+            *value += other;
+        }
+    }
+}
+
+struct Collapse {
+    cost: f64,
+    from: u32,
+    to: u32,
+    target: DVec3,
+}
+
+/// Collapses edges in order of quadric error until at most `target` triangles remain.
+///
+/// Works on vertex indices, so split vertices at UV seams and hard edges show up as open edges. Vertices flagged in
+/// `locked` never move, and edges between two locked vertices are never collapsed. Returns the reduced mesh along
+/// with the new index of every original vertex that survived.
+pub fn decimate(mesh: &Mesh, target: usize, locked: &[bool]) -> (Mesh, Vec<Option<u32>>) {
+    let mut decimator = Decimator::new(mesh, locked);
+    for vertex in 0..decimator.mesh.positions.len() as u32 {
+        decimator.push_edges(vertex);
+    }
+
+    while decimator.mesh.remaining > target {
+        let Reverse((_, from, to, from_stamp, to_stamp)) = match decimator.heap.pop() {
+            Some(entry) => entry,
+            None => break,
+        };
+        if decimator.stamps[from as usize] != from_stamp || decimator.stamps[to as usize] != to_stamp {
+            continue;
+        }
+
+        if let Some(collapse) = decimator
+            .plan(from, to)
+            .filter(|collapse| decimator.mesh.can_collapse(collapse.from, collapse.to, collapse.target))
+        {
+            decimator.collapse(collapse);
+        }
+    }
+
+    let (kept, remap, indices) = decimator.mesh.compact();
+    let mut result = geometry::gather(mesh, &kept);
+    geometry::set_positions(
+        &mut result,
+        &kept
+            .iter()
+            .map(|&idx| decimator.mesh.positions[idx as usize].as_vec3())
+            .collect::<Vec<Vec3>>(),
+    );
+    geometry::set_indices(&mut result, indices);
+    geometry::compute_normals(&mut result);
+    (result, remap)
+}
+
+/// Vertices that [`decimate`] has to keep in place: UV seams and hard edges always, open boundaries on request.
+///
+/// Both seams and boundaries are edges with a single triangle when looking at vertex indices; seams are told apart
+/// by having more triangles once vertices sharing a position are considered the same point.
+pub fn locked_vertices(mesh: &Mesh, preserve_boundaries: bool) -> Vec<bool> {
+    let topology = Topology::new(mesh);
+    let point_edges = topology.edge_faces();
+
+    let mut vertex_edges = HashMap::new();
+    for [a, b, c] in geometry::triangles(mesh) {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            *vertex_edges.entry((from.min(to), from.max(to))).or_insert(0) += 1;
+        }
+    }
+
+    let mut locked = vec![false; topology.vertex_points.len()];
+    for ((a, b), count) in vertex_edges {
+        if count != 1 {
+            continue;
+        }
+        let (pa, pb) = (topology.vertex_points[a as usize], topology.vertex_points[b as usize]);
+        let is_seam = point_edges.get(&(pa.min(pb), pa.max(pb))).copied().unwrap_or(0) > 1;
+        if is_seam || preserve_boundaries {
+            locked[a as usize] = true;
+            locked[b as usize] = true;
+        }
+    }
+    locked
+}
+
+/// Cost bits, collapsed vertex, kept vertex and the stamps of both when the entry was queued.
+type QueuedCollapse = Reverse<(u64, u32, u32, u32, u32)>;
+
+struct Decimator<'a> {
+    locked: &'a [bool],
+    mesh: EditMesh,
+    quadrics: Vec<Quadric>,
+    /// Bumped whenever a vertex changes so that queued collapses computed from its old state are skipped.
+    stamps: Vec<u32>,
+    heap: BinaryHeap<QueuedCollapse>,
+}
+
+impl<'a> Decimator<'a> {
+    fn new(mesh: &Mesh, locked: &'a [bool]) -> Self {
+        let mesh = EditMesh::from_mesh(mesh);
+
+        let mut quadrics = vec![Quadric::default(); mesh.positions.len()];
+        for tri in &mesh.triangles {
+            let cross = mesh.normal(*tri);
+            let normal = cross.normalize_or_zero();
+            let mut quadric = Quadric::from_plane(normal, -normal.dot(mesh.positions[tri[0] as usize]));
+            // weighting by area keeps large flat regions from being dominated by slivers
+            let area = cross.length() * 0.5;
+            for value in quadric.0.iter_mut() {
+                *value *= area;
+            }
+            for &idx in tri {
+                quadrics[idx as usize] += quadric;
+            }
+        }
+
+        Self {
+            locked,
+            stamps: vec![0; mesh.positions.len()],
+            mesh,
+            quadrics,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn push_edges(&mut self, vertex: u32) {
+        for other in self.mesh.neighbors(vertex) {
+            if let Some(collapse) = self.plan(vertex, other) {
+                self.heap.push(Reverse((
+                    collapse.cost.to_bits(),
+                    collapse.from,
+                    collapse.to,
+                    self.stamps[collapse.from as usize],
+                    self.stamps[collapse.to as usize],
+                )));
+            }
+        }
+    }
+
+    fn plan(&self, a: u32, b: u32) -> Option<Collapse> {
+        let quadric = self.quadrics[a as usize] + self.quadrics[b as usize];
+        let (pa, pb) = (self.mesh.positions[a as usize], self.mesh.positions[b as usize]);
+
+        let (from, to, target) = match (self.locked[a as usize], self.locked[b as usize]) {
+            (true, true) => return None,
+            (true, false) => (b, a, pa),
+            (false, true) => (a, b, pb),
+            (false, false) => {
+                let midpoint = (pa + pb) * 0.5;
+                let target = quadric
+                    .optimum()
+                    .filter(|optimum| optimum.distance(midpoint) <= pa.distance(pb) * 2.0)
+                    .unwrap_or_else(|| {
+                        [pa, pb, midpoint]
+                            .into_iter()
+                            .min_by(|x, y| quadric.error(*x).total_cmp(&quadric.error(*y)))
+                            .unwrap()
+                    });
+                (a, b, target)
+            },
+        };
+
+        Some(Collapse {
+            cost: quadric.error(target),
+            from,
+            to,
+            target,
+        })
+    }
+
+    fn collapse(&mut self, collapse: Collapse) {
+        let Collapse { from, to, target, .. } = collapse;
+        self.mesh.collapse(from, to, target);
+
+        let merged = self.quadrics[from as usize] + self.quadrics[to as usize];
+        self.quadrics[to as usize] = merged;
+        self.stamps[from as usize] += 1;
+        self.stamps[to as usize] += 1;
+        self.push_edges(to);
+    }
+}
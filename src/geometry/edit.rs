@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+
+use bevy::{math::DVec3, prelude::Mesh};
+
+use crate::geometry;
+
+/// Triangle soup with vertex-to-triangle adjacency that supports the local edits used by decimation and remeshing.
+///
+/// Removed triangles are only flagged dead so that triangle indices stay stable while editing; [`EditMesh::compact`]
+/// drops them at the end.
+pub struct EditMesh {
+    pub positions: Vec<DVec3>,
+    pub triangles: Vec<[u32; 3]>,
+    pub alive: Vec<bool>,
+    pub remaining: usize,
+    pub vertex_triangles: Vec<Vec<usize>>,
+}
+
+impl EditMesh {
+    pub fn new(positions: Vec<DVec3>, triangles: Vec<[u32; 3]>) -> Self {
+        let mut vertex_triangles = vec![Vec::new(); positions.len()];
+        for (tri_idx, tri) in triangles.iter().enumerate() {
+            for &idx in tri {
+                vertex_triangles[idx as usize].push(tri_idx);
+            }
+        }
+
+        Self {
+            alive: vec![true; triangles.len()],
+            remaining: triangles.len(),
+            positions,
+            triangles,
+            vertex_triangles,
+        }
+    }
+
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        Self::new(
+            geometry::positions(mesh)
+                .into_iter()
+                .map(|position| position.as_dvec3())
+                .collect(),
+            geometry::triangles(mesh),
+        )
+    }
+
+    pub fn alive_triangles(&self) -> impl Iterator<Item = (usize, [u32; 3])> + '_ {
+        self.triangles
+            .iter()
+            .enumerate()
+            .filter(|(tri_idx, _)| self.alive[*tri_idx])
+            .map(|(tri_idx, tri)| (tri_idx, *tri))
+    }
+
+    pub fn triangles_of(&self, vertex: u32) -> impl Iterator<Item = usize> + '_ {
+        self.vertex_triangles[vertex as usize]
+            .iter()
+            .copied()
+            .filter(|&tri_idx| self.alive[tri_idx])
+    }
+
+    pub fn neighbors(&self, vertex: u32) -> HashSet<u32> {
+        self.triangles_of(vertex)
+            .flat_map(|tri_idx| self.triangles[tri_idx])
+            .filter(|&other| other != vertex)
+            .collect()
+    }
+
+    /// Alive triangles containing the edge between `a` and `b`.
+    pub fn edge_triangles(&self, a: u32, b: u32) -> Vec<usize> {
+        self.triangles_of(a)
+            .filter(|&tri_idx| self.triangles[tri_idx].contains(&b))
+            .collect()
+    }
+
+    pub fn normal(&self, tri: [u32; 3]) -> DVec3 {
+        let [a, b, c] = tri.map(|idx| self.positions[idx as usize]);
+        (b - a).cross(c - a)
+    }
+
+    pub fn add_vertex(&mut self, position: DVec3) -> u32 {
+        self.positions.push(position);
+        self.vertex_triangles.push(Vec::new());
+        self.positions.len() as u32 - 1
+    }
+
+    pub fn add_triangle(&mut self, tri: [u32; 3]) -> usize {
+        let tri_idx = self.triangles.len();
+        self.triangles.push(tri);
+        self.alive.push(true);
+        self.remaining += 1;
+        for idx in tri {
+            self.vertex_triangles[idx as usize].push(tri_idx);
+        }
+        tri_idx
+    }
+
+    pub fn remove_triangle(&mut self, tri_idx: usize) {
+        if self.alive[tri_idx] {
+            self.alive[tri_idx] = false;
+            self.remaining -= 1;
+        }
+    }
+
+    /// Rejects collapses of `from` into `to` (moved to `target`) that would make the surface non-manifold or flip
+    /// the triangles around either vertex.
+    pub fn can_collapse(&self, from: u32, to: u32, target: DVec3) -> bool {
+        let shared_triangles = self.edge_triangles(from, to).len();
+        if shared_triangles == 0 {
+            return false;
+        }
+
+        let to_neighbors = self.neighbors(to);
+        let common = self
+            .neighbors(from)
+            .iter()
+            .filter(|vertex| to_neighbors.contains(vertex))
+            .count();
+        if common != shared_triangles {
+            return false;
+        }
+
+        [from, to].iter().all(|&moved| {
+            self.triangles_of(moved)
+                .filter(|&tri_idx| {
+                    let tri = &self.triangles[tri_idx];
+                    !(tri.contains(&from) && tri.contains(&to))
+                })
+                .all(|tri_idx| {
+                    let tri = self.triangles[tri_idx];
+                    let after = tri.map(|idx| {
+                        if idx == from || idx == to {
+                            target
+                        } else {
+                            self.positions[idx as usize]
+                        }
+                    });
+                    let normal_after = (after[1] - after[0]).cross(after[2] - after[0]);
+                    normal_after.length_squared() > 0.0 && self.normal(tri).dot(normal_after) > 0.0
+                })
+        })
+    }
+
+    /// Merges `from` into `to`, moving `to` to `target` and dropping the triangles along the collapsed edge.
+    pub fn collapse(&mut self, from: u32, to: u32, target: DVec3) {
+        for tri_idx in std::mem::take(&mut self.vertex_triangles[from as usize]) {
+            if !self.alive[tri_idx] {
+                continue;
+            }
+            if self.triangles[tri_idx].contains(&to) {
+                self.remove_triangle(tri_idx);
+            } else {
+                for idx in self.triangles[tri_idx].iter_mut() {
+                    if *idx == from {
+                        *idx = to;
+                    }
+                }
+                self.vertex_triangles[to as usize].push(tri_idx);
+            }
+        }
+
+        let alive = &self.alive;
+        self.vertex_triangles[to as usize].retain(|&tri_idx| alive[tri_idx]);
+        self.positions[to as usize] = target;
+    }
+
+    /// Replaces the edge `a`-`b` shared by two triangles with the edge between their opposite corners.
+    pub fn flip(&mut self, a: u32, b: u32) -> bool {
+        let shared = self.edge_triangles(a, b);
+        if shared.len() != 2 {
+            return false;
+        }
+
+        let opposite = |tri: [u32; 3]| tri.into_iter().find(|&idx| idx != a && idx != b).unwrap();
+        let (first, second) = (self.triangles[shared[0]], self.triangles[shared[1]]);
+        let (c, d) = (opposite(first), opposite(second));
+        if c == d || self.neighbors(c).contains(&d) {
+            return false;
+        }
+
+        // keep the winding of the first triangle: it runs a -> b -> c or b -> a -> c
+        let forward = (0..3).any(|idx| first[idx] == a && first[(idx + 1) % 3] == b);
+        let (new_first, new_second) = if forward {
+            ([c, d, b], [d, c, a])
+        } else {
+            ([d, c, b], [c, d, a])
+        };
+        if self.normal(new_first).dot(self.normal(first)) <= 0.0
+            || self.normal(new_second).dot(self.normal(first)) <= 0.0
+        {
+            return false;
+        }
+
+        self.remove_triangle(shared[0]);
+        self.remove_triangle(shared[1]);
+        self.add_triangle(new_first);
+        self.add_triangle(new_second);
+        true
+    }
+
+    /// Drops dead triangles and unused vertices. Returns the surviving original vertex indices in their new order,
+    /// the new index of every original vertex and the new triangle indices.
+    pub fn compact(&self) -> (Vec<u32>, Vec<Option<u32>>, Vec<u32>) {
+        let mut remap = vec![None; self.positions.len()];
+        let mut kept = Vec::new();
+        let mut indices = Vec::with_capacity(self.remaining * 3);
+        for (_, tri) in self.alive_triangles() {
+            for idx in tri {
+                let new_idx = *remap[idx as usize].get_or_insert_with(|| {
+                    kept.push(idx);
+                    kept.len() as u32 - 1
+                });
+                indices.push(new_idx);
+            }
+        }
+        (kept, remap, indices)
+    }
+}
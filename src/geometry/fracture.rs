@@ -0,0 +1,393 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{Mesh, Vec2, Vec3},
+    render::mesh::{PrimitiveTopology, VertexAttributeValues},
+};
+
+use crate::geometry::{self, SpatialHash};
+
+/// Piece of a fractured mesh, with the vertices of its cut faces listed in `inside`.
+pub struct Fragment {
+    pub mesh: Mesh,
+    pub inside: Vec<u32>,
+}
+
+/// Splits a closed mesh into the Voronoi cells of `seeds`: every fragment is the part of the mesh closer to its seed
+/// than to any other, with the cuts closed off by flat faces. Fragments come in seed order, and seeds whose cell
+/// misses the mesh have none. Seeds that coincide with an earlier seed are left out, since they would share its cell.
+///
+/// Positions, normals and UVs are kept, interpolated along the cuts; cut faces get their plane as normal and UVs
+/// projected onto it.
+pub fn voronoi_fracture(mesh: &Mesh, seeds: &[Vec3]) -> Vec<Fragment> {
+    let (min, max) = geometry::aabb(&geometry::positions(mesh));
+    let epsilon = (max - min).length().max(f32::EPSILON) * 1e-5;
+    let piece = Piece::from_mesh(mesh, epsilon);
+
+    let mut unique: Vec<Vec3> = Vec::with_capacity(seeds.len());
+    let mut grid = SpatialHash::new(epsilon);
+    for &seed in seeds {
+        if grid
+            .neighbors(seed)
+            .all(|idx| unique[idx as usize].distance(seed) > epsilon)
+        {
+            grid.insert(unique.len() as u32, seed);
+            unique.push(seed);
+        }
+    }
+
+    let mut fragments = Vec::new();
+    for (idx, seed) in unique.iter().enumerate() {
+        let mut others = unique
+            .iter()
+            .enumerate()
+            .filter(|(other_idx, _)| *other_idx != idx)
+            .map(|(_, other)| *other)
+            .collect::<Vec<_>>();
+        // the closest neighbours shrink the cell the most, which leaves less to cut for the rest
+        others.sort_by(|a, b| a.distance_squared(*seed).total_cmp(&b.distance_squared(*seed)));
+
+        let mut cell = piece.clone();
+        for other in others {
+            let normal = (other - *seed).normalize();
+            cell.clip(normal, normal.dot((other + *seed) / 2.0), epsilon);
+            if cell.triangles.is_empty() {
+                break;
+            }
+        }
+        if !cell.triangles.is_empty() {
+            fragments.push(cell.into_fragment());
+        }
+    }
+    fragments
+}
+
+/// Triangle soup being cut down to a cell.
+#[derive(Clone)]
+struct Piece {
+    positions: Vec<Vec3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<Vec2>,
+    /// Identity of every vertex by position, shared by the copies of a point on either side of a seam so that cuts
+    /// can be traced across them.
+    welds: Vec<u32>,
+    next_weld: u32,
+    triangles: Vec<[u32; 3]>,
+    /// Whether a triangle belongs to a cut face.
+    inside: Vec<bool>,
+}
+
+impl Piece {
+    fn from_mesh(mesh: &Mesh, epsilon: f32) -> Self {
+        let (_, welds) = geometry::weld(mesh, epsilon, false);
+        let mut positions = geometry::positions(mesh);
+        // copies of a point have to sit in exactly the same spot for the cuts through them to meet
+        let mut first = HashMap::new();
+        for (idx, weld) in welds.iter().enumerate() {
+            positions[idx] = *first.entry(*weld).or_insert(positions[idx]);
+        }
+        let normals = geometry::normals(mesh).unwrap_or_else(|| vec![Vec3::ZERO; positions.len()]);
+        let uvs = match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => uvs.iter().map(|uv| Vec2::from(*uv)).collect(),
+            _ => vec![Vec2::ZERO; positions.len()],
+        };
+        let triangles = geometry::triangles(mesh);
+        Self {
+            positions,
+            normals,
+            uvs,
+            next_weld: welds.iter().max().map_or(0, |weld| weld + 1),
+            welds,
+            inside: vec![false; triangles.len()],
+            triangles,
+        }
+    }
+
+    fn push_vertex(&mut self, position: Vec3, normal: Vec3, uv: Vec2, weld: u32) -> u32 {
+        self.positions.push(position);
+        self.normals.push(normal);
+        self.uvs.push(uv);
+        self.welds.push(weld);
+        self.positions.len() as u32 - 1
+    }
+
+    /// Keeps the part behind the plane `normal . x = offset` and closes the cut.
+    fn clip(&mut self, normal: Vec3, offset: f32, epsilon: f32) {
+        let distances = self
+            .positions
+            .iter()
+            .map(|position| position.dot(normal) - offset)
+            .collect::<Vec<_>>();
+        let used = self.triangles.iter().flatten();
+        if used.clone().all(|&vertex| distances[vertex as usize] <= epsilon) {
+            return;
+        }
+        if used.clone().all(|&vertex| distances[vertex as usize] > -epsilon) {
+            self.triangles.clear();
+            self.inside.clear();
+            return;
+        }
+
+        let mut on_plane = distances
+            .iter()
+            .map(|distance| distance.abs() <= epsilon)
+            .collect::<Vec<_>>();
+        let mut near_plane = SpatialHash::new(epsilon);
+        for (vertex, _) in on_plane.iter().enumerate().filter(|(_, on_plane)| **on_plane) {
+            near_plane.insert(vertex as u32, self.positions[vertex]);
+        }
+        let mut cut_vertices = HashMap::new();
+        let mut cut_welds = HashMap::new();
+        let mut triangles = Vec::with_capacity(self.triangles.len());
+        let mut inside = Vec::with_capacity(self.triangles.len());
+        for (tri_idx, triangle) in std::mem::take(&mut self.triangles).into_iter().enumerate() {
+            let outside = triangle.map(|vertex| distances[vertex as usize] > epsilon);
+            if !outside.contains(&true) {
+                triangles.push(triangle);
+                inside.push(self.inside[tri_idx]);
+                continue;
+            }
+            if !outside.contains(&false) {
+                continue;
+            }
+
+            let mut polygon = Vec::with_capacity(4);
+            for corner in 0..3 {
+                let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+                if !outside[corner] {
+                    polygon.push(from);
+                }
+                if outside[corner] == outside[(corner + 1) % 3] {
+                    continue;
+                }
+                let (kept, dropped) = if outside[corner] { (to, from) } else { (from, to) };
+                if on_plane[kept as usize] {
+                    continue;
+                }
+                let key = (kept.min(dropped), kept.max(dropped));
+                let vertex = match cut_vertices.get(&key) {
+                    Some(&vertex) => vertex,
+                    None => {
+                        let t = distances[kept as usize] / (distances[kept as usize] - distances[dropped as usize]);
+                        let weld_key = {
+                            let (a, b) = (self.welds[kept as usize], self.welds[dropped as usize]);
+                            (a.min(b), a.max(b))
+                        };
+                        // copies of a cut across a seam share one spot, and a cut landing on a point already on the
+                        // plane becomes that point, so that no slivers are left between them
+                        let (position, weld) = match cut_welds.get(&weld_key) {
+                            Some(&cut) => cut,
+                            None => {
+                                let position = self.positions[kept as usize].lerp(self.positions[dropped as usize], t);
+                                let cut = match near_plane
+                                    .neighbors(position)
+                                    .find(|&vertex| self.positions[vertex as usize].distance(position) <= epsilon)
+                                {
+                                    Some(vertex) => (self.positions[vertex as usize], self.welds[vertex as usize]),
+                                    None => {
+                                        self.next_weld += 1;
+                                        (position, self.next_weld - 1)
+                                    },
+                                };
+                                cut_welds.insert(weld_key, cut);
+                                cut
+                            },
+                        };
+                        let [kept, dropped] = [kept, dropped].map(|vertex| vertex as usize);
+                        let vertex = self.push_vertex(
+                            position,
+                            self.normals[kept].lerp(self.normals[dropped], t),
+                            self.uvs[kept].lerp(self.uvs[dropped], t),
+                            weld,
+                        );
+                        near_plane.insert(vertex, position);
+                        on_plane.push(true);
+                        cut_vertices.insert(key, vertex);
+                        vertex
+                    },
+                };
+                polygon.push(vertex);
+            }
+            for idx in 1..polygon.len().saturating_sub(1) {
+                let triangle = [polygon[0], polygon[idx], polygon[idx + 1]];
+                let [a, b, c] = triangle.map(|vertex| self.welds[vertex as usize]);
+                if a != b && b != c && c != a {
+                    triangles.push(triangle);
+                    inside.push(self.inside[tri_idx]);
+                }
+            }
+        }
+        self.triangles = triangles;
+        self.inside = inside;
+        self.cap(normal, &on_plane);
+    }
+
+    /// Closes the holes that the last cut left in the plane with the given normal, tracing their rims through the
+    /// edges on the plane that only one triangle uses.
+    fn cap(&mut self, normal: Vec3, on_plane: &[bool]) {
+        let mut half_edges = HashMap::<_, i32>::new();
+        let mut representatives = HashMap::new();
+        for triangle in &self.triangles {
+            for corner in 0..3 {
+                let (from, to) = (triangle[corner], triangle[(corner + 1) % 3]);
+                *half_edges
+                    .entry((self.welds[from as usize], self.welds[to as usize]))
+                    .or_default() += 1;
+                representatives.insert(self.welds[from as usize], from);
+            }
+        }
+        // the cap runs against the rim of the surface, counting edges so that rims touching in a point still close
+        let mut next = HashMap::<_, Vec<_>>::new();
+        for (&(from, to), &count) in &half_edges {
+            let unmatched = count - half_edges.get(&(to, from)).copied().unwrap_or(0);
+            if from != to
+                && unmatched > 0
+                && on_plane[representatives[&from] as usize]
+                && on_plane[representatives[&to] as usize]
+            {
+                let following = next.entry(to).or_default();
+                for _ in 0..unmatched {
+                    following.push(from);
+                }
+            }
+        }
+
+        let mut starts = next.keys().copied().collect::<Vec<_>>();
+        starts.sort_unstable();
+        let mut loops = Vec::new();
+        for start in starts {
+            while !next[&start].is_empty() {
+                let mut ring = Vec::new();
+                let mut weld = start;
+                while let Some(following) = next.get_mut(&weld).and_then(Vec::pop) {
+                    ring.push(weld);
+                    weld = following;
+                    if weld == start {
+                        break;
+                    }
+                }
+                if ring.len() >= 3 {
+                    loops.push(ring);
+                }
+            }
+        }
+
+        let flat = loops
+            .iter()
+            .map(|ring| {
+                let points = ring
+                    .iter()
+                    .map(|weld| self.positions[representatives[weld] as usize])
+                    .collect::<Vec<_>>();
+                geometry::project_to_plane(&points, normal)
+            })
+            .collect::<Vec<_>>();
+        let areas = flat.iter().map(|ring| geometry::signed_area(ring)).collect::<Vec<_>>();
+
+        // rims around material wind counter-clockwise seen from outside, and rims around holes in it the other way
+        let mut holes = vec![Vec::new(); loops.len()];
+        let mut orphans = Vec::new();
+        for (hole, area) in areas.iter().enumerate() {
+            if *area > 0.0 {
+                continue;
+            }
+            let outer = (0..loops.len())
+                .filter(|&outer| areas[outer] > 0.0 && geometry::loop_contains(&flat[outer], flat[hole][0]))
+                .min_by(|&a, &b| areas[a].total_cmp(&areas[b]));
+            match outer {
+                Some(outer) => holes[outer].push(hole),
+                None => orphans.push(hole),
+            }
+        }
+
+        for (outer, area) in areas.iter().enumerate() {
+            if *area <= 0.0 {
+                continue;
+            }
+            let hole_points = holes[outer].iter().map(|&hole| flat[hole].clone()).collect::<Vec<_>>();
+            let welds = std::iter::once(outer)
+                .chain(holes[outer].iter().copied())
+                .flat_map(|ring| loops[ring].iter().copied())
+                .collect::<Vec<_>>();
+            let points = std::iter::once(&flat[outer])
+                .chain(&hole_points)
+                .flatten()
+                .copied()
+                .collect::<Vec<_>>();
+            let vertices = welds
+                .iter()
+                .zip(&points)
+                .map(|(&weld, &uv)| {
+                    let position = self.positions[representatives[&weld] as usize];
+                    self.push_vertex(position, normal, uv, weld)
+                })
+                .collect::<Vec<_>>();
+            for triangle in geometry::triangulate_with_holes(&flat[outer], &hole_points) {
+                self.triangles.push(triangle.map(|corner| vertices[corner as usize]));
+                self.inside.push(true);
+            }
+        }
+
+        // rims winding backwards outside of any other are slivers left by rounding, closed facing away
+        for orphan in orphans {
+            let reversed = flat[orphan].iter().rev().copied().collect::<Vec<_>>();
+            let vertices = loops[orphan]
+                .iter()
+                .zip(&flat[orphan])
+                .map(|(&weld, &uv)| {
+                    let position = self.positions[representatives[&weld] as usize];
+                    self.push_vertex(position, normal, uv, weld)
+                })
+                .collect::<Vec<_>>();
+            for [a, b, c] in geometry::triangulate_loop(&reversed) {
+                let [a, b, c] = [a, b, c].map(|corner| vertices[reversed.len() - 1 - corner as usize]);
+                self.triangles.push([a, c, b]);
+                self.inside.push(true);
+            }
+        }
+    }
+
+    fn into_fragment(self) -> Fragment {
+        let mut remap = vec![None; self.positions.len()];
+        let mut order = Vec::new();
+        let mut inside_vertices = Vec::new();
+        let mut indices = Vec::with_capacity(self.triangles.len() * 3);
+        for (triangle, &inside) in self.triangles.iter().zip(&self.inside) {
+            for &vertex in triangle {
+                let new = *remap[vertex as usize].get_or_insert_with(|| {
+                    order.push(vertex as usize);
+                    if inside {
+                        inside_vertices.push(order.len() as u32 - 1);
+                    }
+                    order.len() as u32 - 1
+                });
+                indices.push(new);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            order
+                .iter()
+                .map(|&idx| self.positions[idx].to_array())
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            order
+                .iter()
+                .map(|&idx| self.normals[idx].normalize_or_zero().to_array())
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            order.iter().map(|&idx| self.uvs[idx].to_array()).collect::<Vec<_>>(),
+        );
+        geometry::set_indices(&mut mesh, indices);
+        Fragment {
+            mesh,
+            inside: inside_vertices,
+        }
+    }
+}
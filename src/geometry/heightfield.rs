@@ -0,0 +1,409 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{Mesh, Vec2, Vec3},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::geometry::{self, Random, Surface, ATTRIBUTE_HEIGHT, ATTRIBUTE_SLOPE, MAX_SAMPLES};
+
+/// Regular grid of heights over the XZ plane, centered on the origin, with named mask layers in `[0, 1]` that have a
+/// value per sample as well. Samples are stored row by row, with rows running along +Z and columns along +X.
+#[derive(Clone)]
+pub struct Heightfield {
+    pub size: Vec2,
+    pub columns: usize,
+    pub rows: usize,
+    pub heights: Vec<f32>,
+    pub masks: HashMap<String, Vec<f32>>,
+}
+
+impl Heightfield {
+    /// Flat heightfield covering `size`, with at least two samples on every side. Sides shorter than `1e-4` are
+    /// widened to that, so that samples never sit on top of each other. Grids of more than [`MAX_SAMPLES`] samples
+    /// have both counts scaled down together until they fit.
+    pub fn new(size: Vec2, columns: usize, rows: usize) -> Self {
+        let (mut columns, mut rows) = (columns.max(2), rows.max(2));
+        let total = columns as f64 * rows as f64;
+        if total > MAX_SAMPLES as f64 {
+            let factor = (MAX_SAMPLES as f64 / total).sqrt();
+            columns = ((columns as f64 * factor) as usize).clamp(2, MAX_SAMPLES / 2);
+            rows = ((rows as f64 * factor) as usize).clamp(2, MAX_SAMPLES / columns);
+        }
+        Self {
+            size: size.max(Vec2::splat(1e-4)),
+            columns,
+            rows,
+            heights: vec![0.0; columns * rows],
+            masks: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heights.is_empty()
+    }
+
+    pub fn index(&self, column: usize, row: usize) -> usize {
+        row * self.columns + column
+    }
+
+    /// Distance between neighbouring samples along X and Z.
+    pub fn spacing(&self) -> Vec2 {
+        self.size / Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32)
+    }
+
+    pub fn height(&self, column: usize, row: usize) -> f32 {
+        self.heights[self.index(column, row)]
+    }
+
+    pub fn position(&self, column: usize, row: usize) -> Vec3 {
+        let planar = Vec2::new(column as f32, row as f32) * self.spacing() - self.size / 2.0;
+        Vec3::new(planar.x, self.height(column, row), planar.y)
+    }
+
+    /// Grid coordinates of a point on the XZ plane, which fall inside `[0, columns - 1] x [0, rows - 1]` when the
+    /// point is over the heightfield.
+    pub fn grid_coordinates(&self, x: f32, z: f32) -> Vec2 {
+        (Vec2::new(x, z) + self.size / 2.0) / self.spacing()
+    }
+
+    /// Bilinearly interpolated height at a point on the XZ plane, or `None` outside of the heightfield.
+    pub fn sample(&self, x: f32, z: f32) -> Option<f32> {
+        let grid = self.grid_coordinates(x, z);
+        let max = Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32);
+        if grid.x < 0.0 || grid.y < 0.0 || grid.x > max.x || grid.y > max.y {
+            return None;
+        }
+        Some(interpolate(&self.heights, self.columns, self.rows, grid))
+    }
+
+    /// Rate of change of the height along X and Z, from central differences.
+    pub fn gradient(&self, column: usize, row: usize) -> Vec2 {
+        let spacing = self.spacing();
+        let (left, right) = (column.saturating_sub(1), (column + 1).min(self.columns - 1));
+        let (back, front) = (row.saturating_sub(1), (row + 1).min(self.rows - 1));
+        Vec2::new(
+            (self.height(right, row) - self.height(left, row)) / ((right - left) as f32 * spacing.x),
+            (self.height(column, front) - self.height(column, back)) / ((front - back) as f32 * spacing.y),
+        )
+    }
+
+    pub fn normal(&self, column: usize, row: usize) -> Vec3 {
+        let gradient = self.gradient(column, row);
+        Vec3::new(-gradient.x, 1.0, -gradient.y).normalize()
+    }
+
+    /// Steepness in radians, from 0 on flat ground to a quarter turn on a cliff.
+    pub fn slope(&self, column: usize, row: usize) -> f32 {
+        self.gradient(column, row).length().atan()
+    }
+
+    pub fn mask(&self, name: &str) -> Option<&[f32]> {
+        self.masks.get(name).map(Vec::as_slice)
+    }
+
+    /// Lowest and highest height.
+    pub fn range(&self) -> (f32, f32) {
+        self.heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &height| {
+                (min.min(height), max.max(height))
+            })
+    }
+
+    /// Grid mesh with a quad face per cell, facing +Y, and UVs spanning the whole heightfield. Every vertex carries
+    /// its height normalized over the heightfield's range in `ATTRIBUTE_HEIGHT` and its slope normalized over a
+    /// quarter turn in `ATTRIBUTE_SLOPE`.
+    pub fn to_surface(&self) -> Surface {
+        let (min, max) = self.range();
+        let span = (max - min).max(f32::EPSILON);
+
+        let mut positions = Vec::with_capacity(self.len());
+        let mut normals = Vec::with_capacity(self.len());
+        let mut uvs = Vec::with_capacity(self.len());
+        let mut heights = Vec::with_capacity(self.len());
+        let mut slopes = Vec::with_capacity(self.len());
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                positions.push(self.position(column, row).to_array());
+                normals.push(self.normal(column, row).to_array());
+                uvs.push([
+                    column as f32 / (self.columns - 1) as f32,
+                    row as f32 / (self.rows - 1) as f32,
+                ]);
+                heights.push((self.height(column, row) - min) / span);
+                slopes.push(self.slope(column, row) / std::f32::consts::FRAC_PI_2);
+            }
+        }
+
+        let mut faces = Vec::with_capacity((self.columns - 1) * (self.rows - 1));
+        for row in 0..self.rows - 1 {
+            for column in 0..self.columns - 1 {
+                let corner = |column: usize, row: usize| self.index(column, row) as u32;
+                faces.push(vec![
+                    corner(column, row),
+                    corner(column, row + 1),
+                    corner(column + 1, row + 1),
+                    corner(column + 1, row),
+                ]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_attribute(ATTRIBUTE_HEIGHT, heights);
+        mesh.insert_attribute(ATTRIBUTE_SLOPE, slopes);
+        geometry::set_indices(&mut mesh, geometry::fan_triangles(&faces));
+        Surface { mesh, faces }
+    }
+}
+
+/// Bilinear interpolation of row by row samples at grid coordinates, clamped to the grid.
+fn interpolate(values: &[f32], columns: usize, rows: usize, grid: Vec2) -> f32 {
+    let grid = grid.clamp(Vec2::ZERO, Vec2::new((columns - 1) as f32, (rows - 1) as f32));
+    let (column, row) = ((grid.x as usize).min(columns - 2), (grid.y as usize).min(rows - 2));
+    let (u, v) = (grid.x - column as f32, grid.y - row as f32);
+    let at = |column: usize, row: usize| values[row * columns + column];
+    let back = at(column, row) * (1.0 - u) + at(column + 1, row) * u;
+    let front = at(column, row + 1) * (1.0 - u) + at(column + 1, row + 1) * u;
+    back * (1.0 - v) + front * v
+}
+
+/// Material sliding down slopes that are steeper than the angle of repose, which wears down cliffs and piles up
+/// scree below them.
+#[derive(Copy, Clone, Debug)]
+pub struct ThermalErosion {
+    pub iterations: u32,
+    /// Angle of repose in radians; slopes below it stay put.
+    pub talus: f32,
+    /// Share of the excess material moved in every iteration, in `[0, 1]`.
+    pub strength: f32,
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self {
+            iterations: 50,
+            talus: 35f32.to_radians(),
+            strength: 0.5,
+        }
+    }
+}
+
+impl ThermalErosion {
+    /// Erodes the heightfield, with the amount moved out of every sample weighted by `mask`.
+    pub fn apply(&self, field: &mut Heightfield, mask: Option<&[f32]>) {
+        let spacing = field.spacing();
+        let neighbours = [(-1, 0), (1, 0), (0, -1), (0, 1), (-1, -1), (1, -1), (-1, 1), (1, 1)].map(|(dx, dz)| {
+            let distance = (Vec2::new(dx as f32, dz as f32) * spacing).length();
+            (dx, dz, self.talus.tan() * distance)
+        });
+
+        let mut moved = vec![0.0; field.len()];
+        for _ in 0..self.iterations {
+            moved.iter_mut().for_each(|amount| *amount = 0.0);
+            for row in 0..field.rows {
+                for column in 0..field.columns {
+                    let idx = field.index(column, row);
+                    let height = field.heights[idx];
+                    let mut excess = [None; 8];
+                    let mut total = 0.0;
+                    let mut steepest = 0.0f32;
+                    for (slot, &(dx, dz, limit)) in excess.iter_mut().zip(&neighbours) {
+                        let (column, row) = (column as isize + dx, row as isize + dz);
+                        if column < 0 || row < 0 || column >= field.columns as isize || row >= field.rows as isize {
+                            continue;
+                        }
+                        let other = field.index(column as usize, row as usize);
+                        let over = height - field.heights[other] - limit;
+                        if over > 0.0 {
+                            *slot = Some((other, over));
+                            total += over;
+                            steepest = steepest.max(over);
+                        }
+                    }
+                    if total <= 0.0 {
+                        continue;
+                    }
+
+                    let weight = mask.map_or(1.0, |mask| mask[idx]);
+                    // moving half of the steepest excess levels that pair without overshooting
+                    let amount = self.strength.clamp(0.0, 1.0) * weight * steepest / 2.0;
+                    moved[idx] -= amount;
+                    for (other, over) in excess.into_iter().flatten() {
+                        moved[other] += amount * over / total;
+                    }
+                }
+            }
+            for (height, amount) in field.heights.iter_mut().zip(&moved) {
+                *height += amount;
+            }
+        }
+    }
+}
+
+/// Rain droplets running downhill, picking up sediment where they speed up and dropping it where they slow down,
+/// which carves gullies and fills valleys. The paths of the droplets are kept in the `flow` mask.
+#[derive(Copy, Clone, Debug)]
+pub struct HydraulicErosion {
+    pub droplets: u32,
+    /// Steps a droplet runs for before it stops.
+    pub lifetime: u32,
+    /// How much a droplet keeps its direction instead of following the slope, in `[0, 1]`.
+    pub inertia: f32,
+    /// Sediment a droplet can carry relative to its speed, water and downhill slope.
+    pub capacity: f32,
+    /// Lower bound on the slope used for the capacity, so that droplets keep eroding on flat ground.
+    pub min_slope: f32,
+    /// Share of the spare capacity picked up per step.
+    pub erosion: f32,
+    /// Share of the excess sediment dropped per step.
+    pub deposition: f32,
+    /// Share of the water that evaporates per step.
+    pub evaporation: f32,
+    pub gravity: f32,
+    pub seed: u64,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            droplets: 20_000,
+            lifetime: 30,
+            inertia: 0.05,
+            capacity: 4.0,
+            min_slope: 0.01,
+            erosion: 0.3,
+            deposition: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+            seed: 0,
+        }
+    }
+}
+
+impl HydraulicErosion {
+    pub const FLOW_MASK: &'static str = "flow";
+
+    /// Erodes the heightfield, with the sediment picked up at every sample weighted by `mask`.
+    ///
+    /// Droplets move one cell per step and the heights are handled in cells as well, so the result does not depend
+    /// on the size of the heightfield, only on its shape.
+    pub fn apply(&self, field: &mut Heightfield, mask: Option<&[f32]>) {
+        let (columns, rows) = (field.columns, field.rows);
+        let cell = (field.spacing().x + field.spacing().y) / 2.0;
+        let mut heights = field.heights.iter().map(|height| height / cell).collect::<Vec<_>>();
+        let mut flow = vec![0.0f32; heights.len()];
+        let mut random = Random::new(self.seed);
+
+        // height and gradient of the cell under a point, in cells
+        let surface = |heights: &[f32], position: Vec2| {
+            let (column, row) = (position.x as usize, position.y as usize);
+            let (u, v) = (position.x - column as f32, position.y - row as f32);
+            let at = |column: usize, row: usize| heights[row * columns + column];
+            let (nw, ne, sw, se) = (
+                at(column, row),
+                at(column + 1, row),
+                at(column, row + 1),
+                at(column + 1, row + 1),
+            );
+            let gradient = Vec2::new(
+                (ne - nw) * (1.0 - v) + (se - sw) * v,
+                (sw - nw) * (1.0 - u) + (se - ne) * u,
+            );
+            let height = nw * (1.0 - u) * (1.0 - v) + ne * u * (1.0 - v) + sw * (1.0 - u) * v + se * u * v;
+            (height, gradient)
+        };
+        let inside = |position: Vec2| {
+            position.x >= 0.0
+                && position.y >= 0.0
+                && position.x < (columns - 1) as f32
+                && position.y < (rows - 1) as f32
+        };
+
+        // samples around a point with their bilinear weights
+        let corners = |position: Vec2| {
+            let (column, row) = (position.x as usize, position.y as usize);
+            let (u, v) = (position.x - column as f32, position.y - row as f32);
+            [
+                (row * columns + column, (1.0 - u) * (1.0 - v)),
+                (row * columns + column + 1, u * (1.0 - v)),
+                ((row + 1) * columns + column, (1.0 - u) * v),
+                ((row + 1) * columns + column + 1, u * v),
+            ]
+        };
+
+        for _ in 0..self.droplets {
+            let mut position = Vec2::new(
+                random.range(0.0, (columns - 1) as f32),
+                random.range(0.0, (rows - 1) as f32),
+            );
+            let mut direction = Vec2::ZERO;
+            let (mut speed, mut water, mut sediment) = (1.0f32, 1.0f32, 0.0f32);
+
+            for _ in 0..self.lifetime {
+                let corners = corners(position);
+                for &(idx, weight) in &corners {
+                    flow[idx] += weight;
+                }
+
+                let (height, gradient) = surface(&heights, position);
+                direction = direction * self.inertia - gradient * (1.0 - self.inertia);
+                if direction.length_squared() < 1e-12 {
+                    direction = Vec2::new(random.range(-1.0, 1.0), random.range(-1.0, 1.0));
+                }
+                direction = direction.normalize();
+                let next = position + direction;
+                if !inside(next) {
+                    break;
+                }
+                let fall = height - surface(&heights, next).0;
+
+                let capacity = fall.max(self.min_slope) * speed * water * self.capacity;
+                if sediment > capacity || fall < 0.0 {
+                    // going uphill the droplet fills the pit behind it, otherwise it drops a share of its excess
+                    let amount = if fall < 0.0 {
+                        (-fall).min(sediment)
+                    } else {
+                        (sediment - capacity) * self.deposition
+                    };
+                    sediment -= amount;
+                    for &(idx, weight) in &corners {
+                        heights[idx] += amount * weight;
+                    }
+                } else {
+                    let amount = ((capacity - sediment) * self.erosion).min(fall);
+                    for &(idx, weight) in &corners {
+                        let taken = amount * weight * mask.map_or(1.0, |mask| mask[idx]);
+                        heights[idx] -= taken;
+                        sediment += taken;
+                    }
+                }
+
+                speed = (speed * speed + fall * self.gravity).max(0.0).sqrt();
+                water *= 1.0 - self.evaporation;
+                position = next;
+            }
+
+            // whatever the droplet still carries settles where it stops, so that no material is lost
+            for (idx, weight) in corners(position) {
+                heights[idx] += sediment * weight;
+            }
+        }
+
+        for (height, scaled) in field.heights.iter_mut().zip(heights) {
+            *height = scaled * cell;
+        }
+        let busiest = flow.iter().copied().fold(0.0, f32::max).max(f32::EPSILON);
+        field.masks.insert(
+            Self::FLOW_MASK.to_string(),
+            flow.into_iter().map(|amount| (amount / busiest).sqrt()).collect(),
+        );
+    }
+}
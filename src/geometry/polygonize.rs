@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+
+use bevy::{
+    prelude::{Mat3, Mesh, Vec3},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::geometry::{self, Surface, Volume};
+
+/// Corners of the six faces of a cell, counterclockwise seen from outside of it. Bits 0, 1 and 2 of a corner step
+/// along X, Y and Z.
+const CELL_FACES: [[usize; 4]; 6] = [[0, 4, 6, 2], [1, 3, 7, 5], [0, 1, 5, 4], [2, 6, 7, 3], [0, 2, 3, 1], [
+    4, 5, 7, 6,
+]];
+
+const CELL_EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (2, 3),
+    (4, 5),
+    (6, 7),
+    (0, 2),
+    (1, 3),
+    (4, 6),
+    (5, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Pull of a dual contouring vertex towards the average of its crossings, which keeps it steady on flat and
+/// rounded parts where the crossings alone do not pin it down.
+const MASS_POINT_WEIGHT: f32 = 0.05;
+
+/// Triangle surface where the volume crosses `iso`, with a vertex on every crossed grid edge.
+///
+/// Instead of the usual case table, the contour of every cell is traced face by face around its surface, and faces
+/// with two opposite corners inside join them when their center is inside as well. Neighbouring cells share that
+/// decision, so the surface has no cracks.
+pub fn marching_cubes(volume: &Volume, iso: f32) -> Surface {
+    let mut builder = Builder::new(volume, iso);
+    let mut faces = Vec::new();
+    for z in 0..volume.dims[2] - 1 {
+        for y in 0..volume.dims[1] - 1 {
+            for x in 0..volume.dims[0] - 1 {
+                let values = [0, 1, 2, 3, 4, 5, 6, 7]
+                    .map(|corner| volume.value(x + (corner & 1), y + (corner >> 1 & 1), z + (corner >> 2 & 1)) - iso);
+                if values.iter().all(|value| *value < 0.0) || values.iter().all(|value| *value >= 0.0) {
+                    continue;
+                }
+
+                // every crossed edge leads to the next one along the contour, keeping the inside on the left
+                let mut links = Vec::with_capacity(12);
+                for face in CELL_FACES {
+                    let crossings = (0..4)
+                        .map(|idx| (face[idx], face[(idx + 1) % 4]))
+                        .filter(|&(from, to)| (values[from] < 0.0) != (values[to] < 0.0))
+                        .collect::<Vec<_>>();
+                    let center_inside = face.iter().map(|&corner| values[corner]).sum::<f32>() < 0.0;
+                    for (idx, &(from, to)) in crossings.iter().enumerate() {
+                        if values[from] >= 0.0 {
+                            continue;
+                        }
+                        let partner = if crossings.len() == 2 || center_inside {
+                            (idx + 1) % crossings.len()
+                        } else {
+                            (idx + crossings.len() - 1) % crossings.len()
+                        };
+                        // neighbouring faces run along a shared edge in opposite directions
+                        let (other_from, other_to) = crossings[partner];
+                        links.push((
+                            (from.min(to), from.max(to)),
+                            (other_from.min(other_to), other_from.max(other_to)),
+                        ));
+                    }
+                }
+
+                while let Some((mut edge, _)) = links.first().copied() {
+                    let mut contour = Vec::new();
+                    while let Some(link) = links.iter().position(|(from, _)| *from == edge) {
+                        contour.push(builder.edge_vertex([x, y, z], edge.0, edge.1));
+                        edge = links.swap_remove(link).1;
+                    }
+                    for idx in 1..contour.len().saturating_sub(1) {
+                        faces.push(vec![contour[0], contour[idx + 1], contour[idx]]);
+                    }
+                }
+            }
+        }
+    }
+    builder.build(faces)
+}
+
+/// Quad surface where the volume crosses `iso`, with a vertex inside every crossed cell placed to best fit the
+/// crossings and their normals, which keeps sharp edges and corners of the shape sharp.
+pub fn dual_contouring(volume: &Volume, iso: f32) -> Surface {
+    let mut builder = Builder::new(volume, iso);
+    let cell_dims = volume.dims.map(|count| count - 1);
+    let cell_index = |[x, y, z]: [usize; 3]| (z * cell_dims[1] + y) * cell_dims[0] + x;
+
+    let mut cell_vertices = vec![None; cell_dims.iter().product()];
+    for z in 0..cell_dims[2] {
+        for y in 0..cell_dims[1] {
+            for x in 0..cell_dims[0] {
+                let corner_position = |corner: usize| [x + (corner & 1), y + (corner >> 1 & 1), z + (corner >> 2 & 1)];
+                let crossings = CELL_EDGES
+                    .iter()
+                    .filter_map(|&(from, to)| {
+                        let [from_value, to_value] = [from, to].map(|corner| {
+                            let [x, y, z] = corner_position(corner);
+                            volume.value(x, y, z) - iso
+                        });
+                        if (from_value < 0.0) == (to_value < 0.0) {
+                            return None;
+                        }
+                        let point = builder.crossing([x, y, z], from, to);
+                        Some((point, volume.gradient(point).normalize_or_zero()))
+                    })
+                    .collect::<Vec<_>>();
+                if crossings.is_empty() {
+                    continue;
+                }
+
+                // least squares fit to the tangent planes of the crossings, in cell units around the cell corner
+                let cell_origin = volume.position(x, y, z);
+                let local = |point: Vec3| (point - cell_origin) / volume.spacing;
+                let mass = crossings.iter().map(|(point, _)| local(*point)).sum::<Vec3>() / crossings.len() as f32;
+                let mut matrix = Mat3::from_diagonal(Vec3::splat(MASS_POINT_WEIGHT));
+                let mut target = mass * MASS_POINT_WEIGHT;
+                for (point, normal) in &crossings {
+                    matrix += Mat3::from_cols(*normal * normal.x, *normal * normal.y, *normal * normal.z);
+                    target += *normal * normal.dot(local(*point));
+                }
+                let fitted = if matrix.determinant().abs() > f32::EPSILON {
+                    matrix.inverse() * target
+                } else {
+                    mass
+                };
+                let position = cell_origin + fitted.clamp(Vec3::ZERO, Vec3::ONE) * volume.spacing;
+                cell_vertices[cell_index([x, y, z])] = Some(builder.push(position));
+            }
+        }
+    }
+
+    let mut faces = Vec::new();
+    for z in 0..volume.dims[2] {
+        for y in 0..volume.dims[1] {
+            for x in 0..volume.dims[0] {
+                let point = [x, y, z];
+                let inside = volume.value(x, y, z) < iso;
+                for axis in 0..3 {
+                    let mut other = point;
+                    other[axis] += 1;
+                    if other[axis] >= volume.dims[axis] || (volume.value(other[0], other[1], other[2]) < iso) == inside
+                    {
+                        continue;
+                    }
+
+                    // the four cells around the edge, counterclockwise around its axis
+                    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                    let quad = [(1, 1), (0, 1), (0, 0), (1, 0)]
+                        .iter()
+                        .map(|&(du, dv)| {
+                            let mut cell = point;
+                            cell[u] = cell[u].checked_sub(du)?;
+                            cell[v] = cell[v].checked_sub(dv)?;
+                            if cell[u] >= cell_dims[u] || cell[v] >= cell_dims[v] || cell[axis] >= cell_dims[axis] {
+                                return None;
+                            }
+                            cell_vertices[cell_index(cell)]
+                        })
+                        .collect::<Option<Vec<_>>>();
+                    if let Some(mut quad) = quad {
+                        // faces point from the inside end of the edge to its outside end
+                        if !inside {
+                            quad.reverse();
+                        }
+                        faces.push(quad);
+                    }
+                }
+            }
+        }
+    }
+    builder.build(faces)
+}
+
+/// Vertices of a surface extracted from a volume, shared between the faces that meet at them.
+struct Builder<'a> {
+    volume: &'a Volume,
+    iso: f32,
+    positions: Vec<Vec3>,
+    edge_vertices: HashMap<(usize, usize), u32>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(volume: &'a Volume, iso: f32) -> Self {
+        Self {
+            volume,
+            iso,
+            positions: Vec::new(),
+            edge_vertices: HashMap::new(),
+        }
+    }
+
+    fn push(&mut self, position: Vec3) -> u32 {
+        self.positions.push(position);
+        self.positions.len() as u32 - 1
+    }
+
+    /// Point where the volume crosses the iso value on the edge between two corners of a cell.
+    fn crossing(&self, cell: [usize; 3], from: usize, to: usize) -> Vec3 {
+        let [from, to] = [from, to].map(|corner| {
+            let [x, y, z] = [0, 1, 2].map(|axis| cell[axis] + (corner >> axis & 1));
+            (self.volume.position(x, y, z), self.volume.value(x, y, z) - self.iso)
+        });
+        let t = (from.1 / (from.1 - to.1)).clamp(0.0, 1.0);
+        from.0.lerp(to.0, t)
+    }
+
+    /// Vertex on the edge between two corners of a cell, created the first time any cell asks for it.
+    fn edge_vertex(&mut self, cell: [usize; 3], from: usize, to: usize) -> u32 {
+        let lower = from.min(to);
+        let [x, y, z] = [0, 1, 2].map(|axis| cell[axis] + (lower >> axis & 1));
+        let key = (self.volume.index(x, y, z), (from ^ to).trailing_zeros() as usize);
+        if let Some(&vertex) = self.edge_vertices.get(&key) {
+            return vertex;
+        }
+        let vertex = self.push(self.crossing(cell, from, to));
+        self.edge_vertices.insert(key, vertex);
+        vertex
+    }
+
+    fn build(self, faces: Vec<Vec<u32>>) -> Surface {
+        let normals = self
+            .positions
+            .iter()
+            .map(|position| self.volume.gradient(*position).normalize_or_zero().to_array())
+            .collect::<Vec<_>>();
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            self.positions
+                .iter()
+                .map(|position| position.to_array())
+                .collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+        geometry::set_indices(&mut mesh, geometry::fan_triangles(&faces));
+        Surface { mesh, faces }
+    }
+}
@@ -0,0 +1,35 @@
+/// Piecewise linear function over `[0, 1]` given by `(position, value)` keys, held constant past the end keys.
+#[derive(Clone, Debug)]
+pub struct Ramp {
+    pub keys: Vec<(f32, f32)>,
+}
+
+impl Ramp {
+    pub fn new(keys: impl Into<Vec<(f32, f32)>>) -> Self {
+        let mut keys = keys.into();
+        keys.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self { keys }
+    }
+
+    pub fn constant(value: f32) -> Self {
+        Self::new([(0.0, value)])
+    }
+
+    pub fn linear(start: f32, end: f32) -> Self {
+        Self::new([(0.0, start), (1.0, end)])
+    }
+
+    pub fn sample(&self, position: f32) -> f32 {
+        let next = self.keys.iter().position(|(key, _)| *key > position);
+        match next {
+            None => self.keys.last().map_or(0.0, |(_, value)| *value),
+            Some(0) => self.keys[0].1,
+            Some(idx) => {
+                let (start, start_value) = self.keys[idx - 1];
+                let (end, end_value) = self.keys[idx];
+                let ratio = (position - start) / (end - start);
+                start_value + (end_value - start_value) * ratio
+            },
+        }
+    }
+}
@@ -0,0 +1,439 @@
+use std::{
+    collections::{HashMap, HashSet},
+    f64::consts::FRAC_PI_2,
+};
+
+use bevy::{math::DVec3, prelude::Mesh, render::mesh::PrimitiveTopology};
+
+use crate::geometry::{self, EditMesh, Topology};
+
+/// Dihedral angle above which an edge is kept as a sharp feature, in radians.
+const FEATURE_ANGLE: f64 = 0.7;
+
+/// Smallest angle cosine between the two triangles of a pair that still makes a planar enough quad.
+const QUAD_PLANARITY: f64 = 0.9;
+
+/// Target edges are kept at least this fraction of the bounding box diagonal long, which bounds the triangle count.
+const MIN_EDGE_RATIO: f32 = 1e-3;
+
+/// Isotropic remeshing after Botsch and Kobbelt: edges are split, collapsed and flipped until they are close to
+/// `edge_length`, then points are relaxed tangentially and projected back onto the input surface.
+///
+/// The edge length is raised to a thousandth of the bounding box diagonal when it is shorter. Open boundaries and
+/// sharp edges stay in place. Only positions and normals are generated, since the new vertices have no counterpart
+/// in the input to take other attributes from.
+pub fn remesh(mesh: &Mesh, edge_length: f32, iterations: u32) -> Mesh {
+    let topology = Topology::new(mesh);
+    let triangles = topology
+        .triangles
+        .iter()
+        .copied()
+        .filter(|&[a, b, c]| a != b && b != c && c != a)
+        .collect::<Vec<_>>();
+    if triangles.is_empty() || edge_length <= 0.0 {
+        return mesh.clone();
+    }
+    let (min, max) = geometry::aabb(&topology.points);
+    let edge_length = edge_length.max(min.distance(max) * MIN_EDGE_RATIO);
+
+    let points = topology.points.iter().map(|point| point.as_dvec3()).collect::<Vec<_>>();
+    let reference = Reference::new(&points, &triangles, edge_length as f64 * 2.0);
+    let mut remesher = Remesher::new(EditMesh::new(points, triangles), edge_length as f64);
+
+    for _ in 0..iterations {
+        remesher.split_long_edges();
+        remesher.collapse_short_edges();
+        remesher.equalize_valences();
+        remesher.relax(&reference);
+    }
+
+    let (kept, _, indices) = remesher.mesh.compact();
+    let mut result = Mesh::new(PrimitiveTopology::TriangleList);
+    result.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        kept.iter()
+            .map(|&idx| remesher.mesh.positions[idx as usize].as_vec3().to_array())
+            .collect::<Vec<_>>(),
+    );
+    geometry::set_indices(&mut result, indices);
+    geometry::compute_normals(&mut result);
+    result
+}
+
+/// Groups the triangles of a mesh into faces, joining neighbouring triangles into quads where the result is planar
+/// and close to rectangular. Triangles without a good partner stay single faces.
+pub fn pair_quads(mesh: &Mesh) -> Vec<Vec<u32>> {
+    let positions = geometry::positions(mesh)
+        .into_iter()
+        .map(|position| position.as_dvec3())
+        .collect::<Vec<_>>();
+    let triangles = geometry::triangles(mesh);
+
+    let mut edge_triangles = HashMap::<(u32, u32), Vec<usize>>::new();
+    for (tri_idx, &[a, b, c]) in triangles.iter().enumerate() {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            edge_triangles
+                .entry((from.min(to), from.max(to)))
+                .or_default()
+                .push(tri_idx);
+        }
+    }
+
+    let mut candidates = edge_triangles
+        .values()
+        .filter(|shared| shared.len() == 2)
+        .filter_map(|shared| {
+            let quad = quad_corners(triangles[shared[0]], triangles[shared[1]])?;
+            let [a, b, c, d] = quad.map(|idx| positions[idx as usize]);
+            let (first, second) = ((b - a).cross(c - a), (c - a).cross(d - a));
+            if first.normalize_or_zero().dot(second.normalize_or_zero()) < QUAD_PLANARITY {
+                return None;
+            }
+
+            let deviation = (0..4)
+                .map(|corner| {
+                    let point = positions[quad[corner] as usize];
+                    let prev = positions[quad[(corner + 3) % 4] as usize] - point;
+                    let next = positions[quad[(corner + 1) % 4] as usize] - point;
+                    (prev.angle_between(next) - FRAC_PI_2).abs()
+                })
+                .fold(0.0, f64::max);
+            (deviation < FRAC_PI_2 * 0.5).then_some((deviation, shared[0], shared[1], quad))
+        })
+        .collect::<Vec<_>>();
+    candidates.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut paired = vec![false; triangles.len()];
+    let mut faces = Vec::with_capacity(triangles.len());
+    for (_, first, second, quad) in candidates {
+        if !paired[first] && !paired[second] {
+            paired[first] = true;
+            paired[second] = true;
+            faces.push(quad.to_vec());
+        }
+    }
+    faces.extend(
+        triangles
+            .iter()
+            .zip(&paired)
+            .filter(|(_, paired)| !**paired)
+            .map(|(tri, _)| tri.to_vec()),
+    );
+    faces
+}
+
+/// Corners of the quad made by two triangles sharing an edge, wound like the triangles.
+fn quad_corners(first: [u32; 3], second: [u32; 3]) -> Option<[u32; 4]> {
+    (0..3).find_map(|corner| {
+        let (from, to, opposite) = (first[corner], first[(corner + 1) % 3], first[(corner + 2) % 3]);
+        let other = (0..3).find(|&idx| second[idx] == to && second[(idx + 1) % 3] == from)?;
+        Some([from, second[(other + 2) % 3], to, opposite])
+    })
+}
+
+/// The input surface, bucketed into a grid so that points can be projected back onto it.
+struct Reference {
+    points: Vec<DVec3>,
+    triangles: Vec<[u32; 3]>,
+    cell_size: f64,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+}
+
+impl Reference {
+    fn new(points: &[DVec3], triangles: &[[u32; 3]], cell_size: f64) -> Self {
+        let cell = |point: DVec3| (point / cell_size).floor().to_array().map(|value| value as i64);
+
+        let mut cells = HashMap::<[i64; 3], Vec<usize>>::new();
+        for (tri_idx, tri) in triangles.iter().enumerate() {
+            let corners = tri.map(|idx| points[idx as usize]);
+            let min = cell(corners[0].min(corners[1]).min(corners[2]));
+            let max = cell(corners[0].max(corners[1]).max(corners[2]));
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        cells.entry([x, y, z]).or_default().push(tri_idx);
+                    }
+                }
+            }
+        }
+
+        Self {
+            points: points.to_vec(),
+            triangles: triangles.to_vec(),
+            cell_size,
+            cells,
+        }
+    }
+
+    /// Closest point on the triangles bucketed around `point`, if there are any.
+    fn project(&self, point: DVec3) -> Option<DVec3> {
+        let [x, y, z] = (point / self.cell_size).floor().to_array().map(|value| value as i64);
+
+        let mut candidates = HashSet::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    candidates.extend(self.cells.get(&[x + dx, y + dy, z + dz]).into_iter().flatten());
+                }
+            }
+        }
+
+        candidates
+            .into_iter()
+            .map(|&tri_idx| {
+                let [a, b, c] = self.triangles[tri_idx].map(|idx| self.points[idx as usize]);
+                closest_point_on_triangle(point, a, b, c)
+            })
+            .min_by(|x, y| x.distance_squared(point).total_cmp(&y.distance_squared(point)))
+    }
+}
+
+/// Closest point to `point` on the triangle `a`, `b`, `c`, following Ericson's region tests.
+pub fn closest_point_on_triangle(point: DVec3, a: DVec3, b: DVec3, c: DVec3) -> DVec3 {
+    let (ab, ac, ap) = (b - a, c - a, point - a);
+    let (d1, d2) = (ab.dot(ap), ac.dot(ap));
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = point - b;
+    let (d3, d4) = (ab.dot(bp), ac.dot(bp));
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = point - c;
+    let (d5, d6) = (ab.dot(cp), ac.dot(cp));
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && d4 - d3 >= 0.0 && d5 - d6 >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    a + ab * (vb * denom) + ac * (vc * denom)
+}
+
+struct Remesher {
+    mesh: EditMesh,
+    edge_length: f64,
+    /// Points on open boundaries or sharp edges, which are never moved or collapsed away.
+    locked: Vec<bool>,
+    boundary: Vec<bool>,
+    features: HashSet<(u32, u32)>,
+}
+
+impl Remesher {
+    fn new(mesh: EditMesh, edge_length: f64) -> Self {
+        let mut edge_triangles = HashMap::<(u32, u32), Vec<usize>>::new();
+        for (tri_idx, [a, b, c]) in mesh.alive_triangles() {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                edge_triangles
+                    .entry((from.min(to), from.max(to)))
+                    .or_default()
+                    .push(tri_idx);
+            }
+        }
+
+        let mut locked = vec![false; mesh.positions.len()];
+        let mut boundary = vec![false; mesh.positions.len()];
+        let mut features = HashSet::new();
+        for (&(a, b), shared) in &edge_triangles {
+            let is_feature = match shared[..] {
+                [first, second] => {
+                    let first = mesh.normal(mesh.triangles[first]).normalize_or_zero();
+                    let second = mesh.normal(mesh.triangles[second]).normalize_or_zero();
+                    first.dot(second) < FEATURE_ANGLE.cos()
+                },
+                [_] => {
+                    boundary[a as usize] = true;
+                    boundary[b as usize] = true;
+                    true
+                },
+                _ => true,
+            };
+            if is_feature {
+                features.insert((a, b));
+                locked[a as usize] = true;
+                locked[b as usize] = true;
+            }
+        }
+
+        Self {
+            mesh,
+            edge_length,
+            locked,
+            boundary,
+            features,
+        }
+    }
+
+    fn edges(&self) -> Vec<(u32, u32)> {
+        let mut edges = self
+            .mesh
+            .alive_triangles()
+            .flat_map(|(_, [a, b, c])| [(a, b), (b, c), (c, a)])
+            .map(|(from, to)| (from.min(to), from.max(to)))
+            .collect::<Vec<_>>();
+        edges.sort_unstable();
+        edges.dedup();
+        edges
+    }
+
+    fn length(&self, a: u32, b: u32) -> f64 {
+        self.mesh.positions[a as usize].distance(self.mesh.positions[b as usize])
+    }
+
+    fn split_long_edges(&mut self) {
+        let max_length = self.edge_length * 4.0 / 3.0;
+        loop {
+            let mut long = self
+                .edges()
+                .into_iter()
+                .filter(|&(a, b)| self.length(a, b) > max_length)
+                .collect::<Vec<_>>();
+            if long.is_empty() {
+                break;
+            }
+            long.sort_by(|&(a, b), &(c, d)| self.length(c, d).total_cmp(&self.length(a, b)));
+
+            for (a, b) in long {
+                let shared = self.mesh.edge_triangles(a, b);
+                if shared.is_empty() {
+                    continue;
+                }
+
+                let midpoint = self
+                    .mesh
+                    .add_vertex((self.mesh.positions[a as usize] + self.mesh.positions[b as usize]) * 0.5);
+                let is_feature = self.features.remove(&(a, b));
+                self.locked.push(is_feature);
+                self.boundary
+                    .push(self.boundary[a as usize] && self.boundary[b as usize] && shared.len() == 1);
+                if is_feature {
+                    self.features.insert((a.min(midpoint), a.max(midpoint)));
+                    self.features.insert((b.min(midpoint), b.max(midpoint)));
+                }
+
+                for tri_idx in shared {
+                    let tri = self.mesh.triangles[tri_idx];
+                    self.mesh.remove_triangle(tri_idx);
+                    self.mesh
+                        .add_triangle(tri.map(|idx| if idx == b { midpoint } else { idx }));
+                    self.mesh
+                        .add_triangle(tri.map(|idx| if idx == a { midpoint } else { idx }));
+                }
+            }
+        }
+    }
+
+    fn collapse_short_edges(&mut self) {
+        let (min_length, max_length) = (self.edge_length * 0.8, self.edge_length * 4.0 / 3.0);
+        let mut short = self
+            .edges()
+            .into_iter()
+            .filter(|&(a, b)| self.length(a, b) < min_length)
+            .collect::<Vec<_>>();
+        short.sort_by(|&(a, b), &(c, d)| self.length(a, b).total_cmp(&self.length(c, d)));
+
+        for (a, b) in short {
+            if self.mesh.edge_triangles(a, b).is_empty() || self.length(a, b) >= min_length {
+                continue;
+            }
+
+            let (from, to, target) = match (self.locked[a as usize], self.locked[b as usize]) {
+                (true, true) => continue,
+                (true, false) => (b, a, self.mesh.positions[a as usize]),
+                (false, true) => (a, b, self.mesh.positions[b as usize]),
+                (false, false) => (
+                    a,
+                    b,
+                    (self.mesh.positions[a as usize] + self.mesh.positions[b as usize]) * 0.5,
+                ),
+            };
+
+            let stays_short = self
+                .mesh
+                .neighbors(from)
+                .union(&self.mesh.neighbors(to))
+                .all(|&other| self.mesh.positions[other as usize].distance(target) <= max_length);
+            if stays_short && self.mesh.can_collapse(from, to, target) {
+                self.mesh.collapse(from, to, target);
+            }
+        }
+    }
+
+    fn equalize_valences(&mut self) {
+        let deviation = |remesher: &Self, vertex: u32, change: i64| {
+            let target = if remesher.boundary[vertex as usize] { 4 } else { 6 };
+            (remesher.mesh.neighbors(vertex).len() as i64 + change - target).abs()
+        };
+
+        for (a, b) in self.edges() {
+            if self.features.contains(&(a, b)) {
+                continue;
+            }
+            let shared = self.mesh.edge_triangles(a, b);
+            if shared.len() != 2 {
+                continue;
+            }
+
+            let opposite = |tri: [u32; 3]| tri.into_iter().find(|&idx| idx != a && idx != b).unwrap();
+            let (c, d) = (
+                opposite(self.mesh.triangles[shared[0]]),
+                opposite(self.mesh.triangles[shared[1]]),
+            );
+            let before = deviation(self, a, 0) + deviation(self, b, 0) + deviation(self, c, 0) + deviation(self, d, 0);
+            let after = deviation(self, a, -1) + deviation(self, b, -1) + deviation(self, c, 1) + deviation(self, d, 1);
+            if after < before {
+                self.mesh.flip(a, b);
+            }
+        }
+    }
+
+    /// Moves every free point towards the centroid of its neighbours within its tangent plane, then back onto the
+    /// input surface.
+    fn relax(&mut self, reference: &Reference) {
+        let mut normals = vec![DVec3::ZERO; self.mesh.positions.len()];
+        for (_, tri) in self.mesh.alive_triangles() {
+            let normal = self.mesh.normal(tri);
+            for idx in tri {
+                normals[idx as usize] += normal;
+            }
+        }
+
+        let relaxed = (0..self.mesh.positions.len() as u32)
+            .map(|vertex| {
+                let position = self.mesh.positions[vertex as usize];
+                let neighbors = self.mesh.neighbors(vertex);
+                if self.locked[vertex as usize] || neighbors.is_empty() {
+                    return position;
+                }
+
+                let centroid = neighbors
+                    .iter()
+                    .map(|&other| self.mesh.positions[other as usize])
+                    .fold(DVec3::ZERO, |sum, position| sum + position)
+                    / neighbors.len() as f64;
+                let normal = normals[vertex as usize].normalize_or_zero();
+                let offset = centroid - position;
+                let moved = position + offset - normal * normal.dot(offset);
+                reference.project(moved).unwrap_or(moved)
+            })
+            .collect();
+        self.mesh.positions = relaxed;
+    }
+}
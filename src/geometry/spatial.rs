@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use bevy::prelude::Vec3;
+
+/// Uniform grid bucketing points by cell so that neighbours within `cell_size` are found in the 27 surrounding cells.
+pub struct SpatialHash {
+    cell_size: f32,
+    cells: HashMap<[i64; 3], Vec<u32>>,
+}
+
+impl SpatialHash {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size: cell_size.max(f32::EPSILON),
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Cell of a position. Coordinates beyond the range of `i64` share the outermost cells.
+    pub fn cell(&self, position: Vec3) -> [i64; 3] {
+        let cell = (position / self.cell_size).floor();
+        [cell.x as i64, cell.y as i64, cell.z as i64]
+    }
+
+    pub fn insert(&mut self, idx: u32, position: Vec3) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push(idx);
+    }
+
+    pub fn remove(&mut self, idx: u32, position: Vec3) {
+        let cell = self.cell(position);
+        if let Some(indices) = self.cells.get_mut(&cell) {
+            indices.retain(|&other| other != idx);
+        }
+    }
+
+    /// Candidates within one cell of `position`; callers still need to check the actual distance.
+    pub fn neighbors(&self, position: Vec3) -> impl Iterator<Item = u32> + '_ {
+        let [x, y, z] = self.cell(position);
+        (-1..=1)
+            .flat_map(move |dx| {
+                (-1..=1).flat_map(move |dy| {
+                    (-1..=1).map(move |dz| [x.saturating_add(dx), y.saturating_add(dy), z.saturating_add(dz)])
+                })
+            })
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .copied()
+    }
+}
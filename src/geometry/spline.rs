@@ -0,0 +1,168 @@
+use bevy::prelude::{Vec3, Vec4};
+
+use crate::geometry;
+
+/// Rational B-spline curve. Closed splines are stored with their first `degree` control points repeated at the end
+/// and uniform knots, which makes the curve wrap around smoothly.
+#[derive(Clone, Debug)]
+pub struct Spline {
+    pub points: Vec<Vec3>,
+    pub weights: Vec<f32>,
+    pub degree: usize,
+    pub knots: Vec<f32>,
+    pub closed: bool,
+}
+
+impl Spline {
+    /// NURBS curve through `points`. Missing weights default to one, and `knots` are only used on open curves when
+    /// they are non-decreasing with one more entry than the points and the order together; otherwise the knots are
+    /// uniform and clamped to the end points.
+    pub fn nurbs(points: Vec<Vec3>, weights: &[f32], degree: usize, knots: Option<Vec<f32>>, closed: bool) -> Self {
+        let degree = degree.clamp(1, points.len().saturating_sub(1).max(1));
+        let mut weights = (0..points.len())
+            .map(|idx| weights.get(idx).copied().unwrap_or(1.0))
+            .collect::<Vec<_>>();
+        let mut points = points;
+
+        if closed && points.len() > degree {
+            points.extend_from_within(..degree);
+            weights.extend_from_within(..degree);
+            let knots = (0..points.len() + degree + 1).map(|idx| idx as f32).collect();
+            return Self {
+                points,
+                weights,
+                degree,
+                knots,
+                closed,
+            };
+        }
+
+        let knots = knots
+            .filter(|knots| knots.len() == points.len() + degree + 1 && knots.windows(2).all(|pair| pair[0] <= pair[1]))
+            .unwrap_or_else(|| clamped_knots(points.len(), degree));
+        Self {
+            points,
+            weights,
+            degree,
+            knots,
+            closed: false,
+        }
+    }
+
+    /// Chain of Bezier segments of `degree` sharing their end points, so every `degree`-th point lies on the curve.
+    /// Trailing points that do not complete a segment are left out.
+    pub fn bezier(points: Vec<Vec3>, degree: usize) -> Self {
+        let degree = degree.clamp(1, points.len().saturating_sub(1).max(1));
+        let segments = (points.len().saturating_sub(1) / degree).max(1);
+        let mut points = points;
+        points.truncate(segments * degree + 1);
+
+        let mut knots = vec![0.0; degree + 1];
+        for segment in 1..segments {
+            knots.resize(knots.len() + degree, segment as f32);
+        }
+        knots.resize(knots.len() + degree + 1, segments as f32);
+        Self {
+            weights: vec![1.0; points.len()],
+            points,
+            degree,
+            knots,
+            closed: false,
+        }
+    }
+
+    /// Parameter range the curve is defined on.
+    pub fn domain(&self) -> (f32, f32) {
+        match self.knots.get(self.degree).zip(self.knots.get(self.points.len())) {
+            Some((start, end)) => (*start, *end),
+            None => (0.0, 0.0),
+        }
+    }
+
+    /// Number of non-empty knot spans in the domain.
+    pub fn spans(&self) -> usize {
+        (self.degree..self.points.len())
+            .filter(|&idx| self.knots[idx + 1] > self.knots[idx])
+            .count()
+    }
+
+    /// Point at parameter `t` by de Boor's algorithm in homogeneous coordinates.
+    pub fn evaluate(&self, t: f32) -> Vec3 {
+        let degree = self.degree;
+        let count = self.points.len();
+        if count <= degree {
+            return self.points.first().copied().unwrap_or_default();
+        }
+
+        let (start, end) = self.domain();
+        let t = t.clamp(start, end);
+        let mut span = degree;
+        while span + 1 < count && self.knots[span + 1] <= t {
+            span += 1;
+        }
+
+        let mut points = (0..=degree)
+            .map(|idx| {
+                let weight = self.weights[span - degree + idx];
+                (self.points[span - degree + idx] * weight).extend(weight)
+            })
+            .collect::<Vec<Vec4>>();
+        for level in 1..=degree {
+            for idx in (level..=degree).rev() {
+                let knot = span - degree + idx;
+                let width = self.knots[knot + 1 + degree - level] - self.knots[knot];
+                let alpha = if width > 0.0 {
+                    (t - self.knots[knot]) / width
+                } else {
+                    0.0
+                };
+                points[idx] = points[idx - 1].lerp(points[idx], alpha);
+            }
+        }
+
+        let point = points[degree];
+        if point.w.abs() > f32::EPSILON {
+            point.truncate() / point.w
+        } else {
+            point.truncate()
+        }
+    }
+
+    /// Polyline through `steps` evenly spaced parameters per knot span. Closed curves leave out the end point, which
+    /// is the same as the start.
+    pub fn sample(&self, steps: usize) -> Vec<Vec3> {
+        let (start, end) = self.domain();
+        let count = (self.spans() * steps.max(1)).max(1);
+        let last = if self.closed { count - 1 } else { count };
+        (0..=last)
+            .map(|idx| self.evaluate(start + (end - start) * idx as f32 / count as f32))
+            .collect()
+    }
+
+    /// Polyline with `segments` segments of equal length along the curve.
+    pub fn resample(&self, segments: usize) -> Vec<Vec3> {
+        let count = if self.closed {
+            segments.max(3)
+        } else {
+            segments.max(1) + 1
+        };
+        geometry::resample(&self.sample(SAMPLES_PER_SPAN), self.closed, count)
+    }
+
+    /// Approximate length of the curve.
+    pub fn length(&self) -> f32 {
+        let lengths = geometry::arc_lengths(&self.sample(SAMPLES_PER_SPAN), self.closed);
+        lengths.last().copied().unwrap_or(0.0)
+    }
+}
+
+/// Density of the polylines used to measure and resample curves.
+const SAMPLES_PER_SPAN: usize = 32;
+
+fn clamped_knots(count: usize, degree: usize) -> Vec<f32> {
+    let interior = count.saturating_sub(degree + 1);
+    let mut knots = vec![0.0; degree + 1];
+    knots.extend((1..=interior).map(|idx| idx as f32 / (interior + 1) as f32));
+    knots.resize(knots.len() + degree + 1, 1.0);
+    knots
+}
@@ -0,0 +1,198 @@
+use bevy::{
+    prelude::{Mesh, Vec2, Vec3},
+    render::mesh::{PrimitiveTopology, VertexAttributeValues},
+};
+
+use crate::geometry;
+
+/// Generated mesh together with its n-gon faces, before it goes into a `ProcessObject`.
+pub struct Surface {
+    pub mesh: Mesh,
+    pub faces: Vec<Vec<u32>>,
+}
+
+impl Surface {
+    /// Quad surface through rings of points that all have the same count, such as copies of a profile placed along
+    /// a path. `v` holds the texture coordinate of every ring, plus one for the closing ring when `rings_closed`.
+    ///
+    /// Closed directions get a duplicated seam so that UVs do not wrap; normals are averaged across seams and other
+    /// coincident points so that shading stays continuous. Empty rings give an empty surface.
+    pub fn rings(rings: &[Vec<Vec3>], profile_closed: bool, rings_closed: bool, v: &[f32]) -> Self {
+        let profile_count = rings.first().map_or(0, Vec::len);
+        if profile_count == 0 {
+            let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+            mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+            mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, Vec::<[f32; 3]>::new());
+            mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, Vec::<[f32; 2]>::new());
+            geometry::set_indices(&mut mesh, Vec::new());
+            return Self {
+                mesh,
+                faces: Vec::new(),
+            };
+        }
+        let columns = profile_count + usize::from(profile_closed);
+        let rows = rings.len() + usize::from(rings_closed);
+
+        let u = match rings.first() {
+            Some(profile) => {
+                let lengths = geometry::arc_lengths(profile, profile_closed);
+                let total = lengths.last().copied().unwrap_or(0.0).max(f32::EPSILON);
+                lengths.into_iter().map(|length| length / total).collect::<Vec<_>>()
+            },
+            None => Vec::new(),
+        };
+
+        let mut positions = Vec::with_capacity(rows * columns);
+        let mut uvs = Vec::with_capacity(rows * columns);
+        for row in 0..rows {
+            let ring = &rings[row % rings.len()];
+            for column in 0..columns {
+                positions.push(ring[column % profile_count].to_array());
+                uvs.push([u[column], v[row]]);
+            }
+        }
+
+        let mut faces = Vec::new();
+        if profile_count >= 2 && rings.len() >= 2 {
+            for row in 0..rows - 1 {
+                for column in 0..columns - 1 {
+                    let corner = |row: usize, column: usize| (row * columns + column) as u32;
+                    faces.push(vec![
+                        corner(row, column),
+                        corner(row, column + 1),
+                        corner(row + 1, column + 1),
+                        corner(row + 1, column),
+                    ]);
+                }
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        geometry::set_indices(&mut mesh, fan_triangles(&faces));
+        geometry::compute_normals(&mut mesh);
+        if let Some(normals) = geometry::point_normals(&mesh) {
+            mesh.insert_attribute(
+                Mesh::ATTRIBUTE_NORMAL,
+                normals.into_iter().map(|normal| normal.to_array()).collect::<Vec<_>>(),
+            );
+        }
+        Self { mesh, faces }
+    }
+
+    /// Flat polygon closing off a loop of points, wound to face along `facing`, with UVs projected onto its plane.
+    pub fn cap(points: &[Vec3], facing: Vec3) -> Self {
+        Self::cap_with_holes(points, &[], facing)
+    }
+
+    /// Flat polygon with holes cut out of it, facing along `facing`. Faces cannot have holes, so with any holes the
+    /// faces are the triangles instead of a single polygon.
+    pub fn cap_with_holes(outer: &[Vec3], holes: &[Vec<Vec3>], facing: Vec3) -> Self {
+        let mut normal = geometry::polygon_normal(outer);
+        if normal.dot(facing) < 0.0 {
+            normal = -normal;
+        }
+
+        // the outer loop has to wind counter-clockwise around the normal and the holes the other way
+        let mut points = Vec::new();
+        let mut projected = Vec::new();
+        let mut projected_holes = Vec::new();
+        for (idx, ring) in std::iter::once(outer)
+            .chain(holes.iter().map(Vec::as_slice))
+            .enumerate()
+        {
+            let mut ring = ring.to_vec();
+            let mut flat = geometry::project_to_plane(&ring, normal);
+            if (geometry::signed_area(&flat) > 0.0) != (idx == 0) {
+                ring.reverse();
+                flat.reverse();
+            }
+            points.extend(ring);
+            match idx {
+                0 => projected = flat,
+                _ => projected_holes.push(flat),
+            }
+        }
+        let triangles = geometry::triangulate_with_holes(&projected, &projected_holes);
+        let projected = projected
+            .into_iter()
+            .chain(projected_holes.into_iter().flatten())
+            .collect::<Vec<_>>();
+
+        let (min, max) = projected
+            .iter()
+            .fold((Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)), |(min, max), point| {
+                (min.min(*point), max.max(*point))
+            });
+        let size = (max - min).max(Vec2::splat(f32::EPSILON));
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            points.iter().map(|point| point.to_array()).collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![normal.to_array(); points.len()]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_UV_0,
+            projected
+                .iter()
+                .map(|point| ((*point - min) / size).to_array())
+                .collect::<Vec<_>>(),
+        );
+        geometry::set_indices(&mut mesh, triangles.iter().flatten().copied().collect());
+
+        let faces = match holes.is_empty() {
+            true => vec![(0..points.len() as u32).collect()],
+            false => triangles.into_iter().map(|triangle| triangle.to_vec()).collect(),
+        };
+        Self { mesh, faces }
+    }
+
+    /// Turns the surface over by reversing its faces and normals.
+    pub fn flip(&mut self) {
+        for face in self.faces.iter_mut() {
+            face.reverse();
+        }
+        let indices = geometry::triangles(&self.mesh)
+            .into_iter()
+            .flat_map(|[a, b, c]| [a, c, b])
+            .collect();
+        geometry::set_indices(&mut self.mesh, indices);
+        if let Some(VertexAttributeValues::Float32x3(normals)) = self.mesh.attribute_mut(Mesh::ATTRIBUTE_NORMAL) {
+            for normal in normals.iter_mut() {
+                *normal = normal.map(|component| -component);
+            }
+        }
+    }
+
+    /// Concatenates surfaces, keeping only the attributes all of them share.
+    pub fn merge(surfaces: Vec<Surface>) -> Option<Self> {
+        let mut faces = Vec::new();
+        let mut offset = 0;
+        let mut meshes = Vec::with_capacity(surfaces.len());
+        for surface in surfaces {
+            faces.extend(
+                surface
+                    .faces
+                    .into_iter()
+                    .map(|face| face.into_iter().map(|vertex| vertex + offset).collect::<Vec<_>>()),
+            );
+            offset += surface.mesh.count_vertices() as u32;
+            meshes.push(surface.mesh);
+        }
+
+        Some(Self {
+            mesh: geometry::merge(&meshes)?,
+            faces,
+        })
+    }
+}
+
+/// Triangle fans over faces, which is exact for the convex faces of generated grids.
+pub fn fan_triangles(faces: &[Vec<u32>]) -> Vec<u32> {
+    faces
+        .iter()
+        .flat_map(|face| (1..face.len().saturating_sub(1)).flat_map(move |idx| [face[0], face[idx], face[idx + 1]]))
+        .collect()
+}
@@ -0,0 +1,97 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::{Mesh, Vec3};
+
+use crate::geometry;
+
+/// Triangle connectivity over points, where a point is every group of vertices sharing a position.
+///
+/// Meshes like `shape::Box` split vertices along hard edges and UV seams; working on points instead keeps those
+/// vertices together when positions are edited.
+pub struct Topology {
+    pub vertex_points: Vec<u32>,
+    pub points: Vec<Vec3>,
+    pub triangles: Vec<[u32; 3]>,
+}
+
+impl Topology {
+    pub fn new(mesh: &Mesh) -> Self {
+        let positions = geometry::positions(mesh);
+        let mut lookup = HashMap::new();
+        let mut points = Vec::new();
+
+        let vertex_points = positions
+            .iter()
+            .map(|position| {
+                *lookup.entry(position.to_array().map(f32::to_bits)).or_insert_with(|| {
+                    points.push(*position);
+                    points.len() as u32 - 1
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let triangles = geometry::triangles(mesh)
+            .into_iter()
+            .map(|tri| tri.map(|idx| vertex_points[idx as usize]))
+            .collect();
+
+        Self {
+            vertex_points,
+            points,
+            triangles,
+        }
+    }
+
+    pub fn neighbors(&self) -> Vec<Vec<u32>> {
+        let mut neighbors = vec![HashSet::new(); self.points.len()];
+        for &[a, b, c] in &self.triangles {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                if from != to {
+                    neighbors[from as usize].insert(to);
+                    neighbors[to as usize].insert(from);
+                }
+            }
+        }
+        neighbors
+            .into_iter()
+            .map(|set| {
+                let mut list = set.into_iter().collect::<Vec<_>>();
+                list.sort_unstable();
+                list
+            })
+            .collect()
+    }
+
+    /// Number of triangles sharing each undirected edge.
+    pub fn edge_faces(&self) -> HashMap<(u32, u32), u32> {
+        let mut edges = HashMap::new();
+        for &[a, b, c] in &self.triangles {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                *edges.entry((from.min(to), from.max(to))).or_insert(0) += 1;
+            }
+        }
+        edges
+    }
+
+    /// Points lying on an edge used by a single triangle.
+    pub fn boundary_points(&self) -> Vec<bool> {
+        let mut boundary = vec![false; self.points.len()];
+        for ((a, b), count) in self.edge_faces() {
+            if count == 1 {
+                boundary[a as usize] = true;
+                boundary[b as usize] = true;
+            }
+        }
+        boundary
+    }
+
+    /// Writes point positions back onto every vertex of `mesh` that belongs to them.
+    pub fn apply(&self, mesh: &mut Mesh, points: &[Vec3]) {
+        let positions = self
+            .vertex_points
+            .iter()
+            .map(|&point| points[point as usize])
+            .collect::<Vec<_>>();
+        geometry::set_positions(mesh, &positions);
+    }
+}
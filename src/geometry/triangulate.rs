@@ -0,0 +1,259 @@
+use bevy::prelude::{Vec2, Vec3};
+
+/// Newell normal of a polygon loop, which stays meaningful for concave and slightly non-planar loops.
+pub fn polygon_normal(points: &[Vec3]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+    for (idx, current) in points.iter().enumerate() {
+        let next = points[(idx + 1) % points.len()];
+        normal += Vec3::new(
+            (current.y - next.y) * (current.z + next.z),
+            (current.z - next.z) * (current.x + next.x),
+            (current.x - next.x) * (current.y + next.y),
+        );
+    }
+    normal.normalize_or_zero()
+}
+
+/// Projects points onto the plane through the origin with the given normal, in a basis where the normal points
+/// towards the viewer, so loops winding counter-clockwise around `normal` stay counter-clockwise in 2D.
+pub fn project_to_plane(points: &[Vec3], normal: Vec3) -> Vec<Vec2> {
+    let reference = if normal.x.abs() < 0.9 { Vec3::X } else { Vec3::Y };
+    let u = reference.cross(normal).normalize_or_zero();
+    let v = normal.cross(u);
+    points
+        .iter()
+        .map(|point| Vec2::new(point.dot(u), point.dot(v)))
+        .collect()
+}
+
+/// Ear clipping triangulation of a simple polygon, concave or not. Returns corners as indices into `points`,
+/// wound the same way as the loop.
+pub fn triangulate_polygon(points: &[Vec3]) -> Vec<[u32; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut normal = polygon_normal(points);
+    if normal == Vec3::ZERO {
+        normal = Vec3::Y;
+    }
+    triangulate_loop(&project_to_plane(points, normal))
+}
+
+/// Ear clipping on a counter-clockwise 2D loop.
+pub fn triangulate_loop(points: &[Vec2]) -> Vec<[u32; 3]> {
+    clip_ears(points, (0..points.len() as u32).collect())
+}
+
+/// Ear clipping of a counter-clockwise outer loop with clockwise holes. Every hole is bridged to a vertex it can
+/// see, which cuts the polygon open into a single loop. Corners index into the outer points followed by the points
+/// of every hole in order.
+pub fn triangulate_with_holes(outer: &[Vec2], holes: &[Vec<Vec2>]) -> Vec<[u32; 3]> {
+    let mut points = outer.to_vec();
+    let mut ranges = Vec::with_capacity(holes.len());
+    for hole in holes {
+        ranges.push(points.len() as u32..(points.len() + hole.len()) as u32);
+        points.extend_from_slice(hole);
+    }
+
+    // holes furthest along x go first, so that the bridges of the later ones can reach around them
+    let rightmost = |range: &std::ops::Range<u32>| {
+        range
+            .clone()
+            .max_by(|a, b| points[*a as usize].x.total_cmp(&points[*b as usize].x))
+    };
+    ranges.retain(|range| range.len() >= 3);
+    ranges.sort_by(|a, b| {
+        let [a, b] = [a, b].map(|range| rightmost(range).map_or(f32::MIN, |idx| points[idx as usize].x));
+        b.total_cmp(&a)
+    });
+
+    let mut polygon = (0..outer.len() as u32).collect::<Vec<_>>();
+    for (hole_idx, range) in ranges.iter().enumerate() {
+        let start = match rightmost(range) {
+            Some(start) => start,
+            None => continue,
+        };
+        let origin = points[start as usize];
+
+        // edges that a bridge must not cross: the loop so far and every hole still to come
+        let mut edges = (0..polygon.len())
+            .map(|idx| (polygon[idx], polygon[(idx + 1) % polygon.len()]))
+            .collect::<Vec<_>>();
+        for other in &ranges[hole_idx..] {
+            edges.extend(
+                other
+                    .clone()
+                    .map(|idx| (idx, other.start + (idx - other.start + 1) % other.len() as u32)),
+            );
+        }
+
+        let mut candidates = (0..polygon.len()).collect::<Vec<_>>();
+        candidates.sort_by(|a, b| {
+            let [a, b] = [a, b].map(|&position| points[polygon[position] as usize].distance_squared(origin));
+            a.total_cmp(&b)
+        });
+        let position = candidates
+            .into_iter()
+            .find(|&position| {
+                let target = polygon[position];
+                let end = points[target as usize];
+                edges.iter().all(|&(a, b)| {
+                    [a, b].contains(&start)
+                        || [a, b].contains(&target)
+                        || !segments_cross(origin, end, points[a as usize], points[b as usize])
+                })
+            })
+            .unwrap_or(0);
+
+        let target = polygon[position];
+        let offset = start - range.start;
+        let hole_loop = (0..=range.len() as u32).map(|step| range.start + (offset + step) % range.len() as u32);
+        let bridge = hole_loop.chain([target]).collect::<Vec<_>>();
+        polygon.splice(position + 1..position + 1, bridge);
+    }
+
+    clip_ears(&points, polygon)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside wherever the loops wind around a point a non-zero number of times.
+    NonZero,
+    /// Inside wherever a point is enclosed by an odd number of loops.
+    EvenOdd,
+}
+
+/// Sorts overlapping 2D loops into outer loops with the holes cut out of them, as the fill rule decides. Outer loops
+/// come out counter-clockwise and holes clockwise, whichever way they were wound; loops that bound nothing under the
+/// rule are left out.
+pub fn nest_loops(loops: Vec<Vec<Vec2>>, rule: FillRule) -> Vec<(Vec<Vec2>, Vec<Vec<Vec2>>)> {
+    let loops = loops.into_iter().filter(|ring| ring.len() >= 3).collect::<Vec<_>>();
+    let areas = loops.iter().map(|ring| signed_area(ring)).collect::<Vec<_>>();
+    let containing = (0..loops.len())
+        .map(|idx| {
+            (0..loops.len())
+                .filter(|&other| other != idx && loop_contains(&loops[other], loops[idx][0]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // whether the area just inside and just outside of every loop is filled
+    let filled = (0..loops.len())
+        .map(|idx| match rule {
+            FillRule::NonZero => {
+                let outside = containing[idx]
+                    .iter()
+                    .map(|&other| areas[other].signum() as i32)
+                    .sum::<i32>();
+                (outside + areas[idx].signum() as i32 != 0, outside != 0)
+            },
+            FillRule::EvenOdd => {
+                let outside = containing[idx].len() % 2 == 1;
+                (!outside, outside)
+            },
+        })
+        .collect::<Vec<_>>();
+
+    let oriented = |idx: usize, counter_clockwise: bool| {
+        let mut ring = loops[idx].clone();
+        if (areas[idx] > 0.0) != counter_clockwise {
+            ring.reverse();
+        }
+        ring
+    };
+
+    let outers = (0..loops.len())
+        .filter(|&idx| filled[idx] == (true, false))
+        .collect::<Vec<_>>();
+    let mut shapes = outers
+        .iter()
+        .map(|&idx| (oriented(idx, true), Vec::new()))
+        .collect::<Vec<_>>();
+    for idx in (0..loops.len()).filter(|&idx| filled[idx] == (false, true)) {
+        // the innermost outer loop around the hole is the one it cuts into
+        let parent = outers
+            .iter()
+            .enumerate()
+            .filter(|(_, &outer)| loop_contains(&loops[outer], loops[idx][0]))
+            .min_by(|(_, &a), (_, &b)| areas[a].abs().total_cmp(&areas[b].abs()))
+            .map(|(position, _)| position);
+        if let Some(parent) = parent {
+            shapes[parent].1.push(oriented(idx, false));
+        }
+    }
+    shapes
+}
+
+fn clip_ears(points: &[Vec2], mut remaining: Vec<u32>) -> Vec<[u32; 3]> {
+    let mut triangles = Vec::with_capacity(remaining.len().saturating_sub(2));
+    let mut cursor = 0;
+    // bounds the search so that degenerate input without any valid ear still terminates
+    let mut attempts = 0;
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let prev = remaining[(cursor + count - 1) % count];
+        let current = remaining[cursor % count];
+        let next = remaining[(cursor + 1) % count];
+
+        if is_ear(points, &remaining, prev, current, next) || attempts > count {
+            triangles.push([prev, current, next]);
+            remaining.remove(cursor % count);
+            cursor %= remaining.len();
+            attempts = 0;
+        } else {
+            cursor = (cursor + 1) % count;
+            attempts += 1;
+        }
+    }
+
+    if let [a, b, c] = remaining[..] {
+        triangles.push([a, b, c]);
+    }
+    triangles
+}
+
+/// Signed area of a 2D loop, positive when it winds counter-clockwise.
+pub fn signed_area(points: &[Vec2]) -> f32 {
+    (0..points.len())
+        .map(|idx| points[idx].perp_dot(points[(idx + 1) % points.len()]))
+        .sum::<f32>()
+        * 0.5
+}
+
+/// Even-odd test of a point against a 2D loop.
+pub fn loop_contains(points: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    for idx in 0..points.len() {
+        let (a, b) = (points[idx], points[(idx + 1) % points.len()]);
+        if (a.y > point.y) != (b.y > point.y) && point.x < a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    cross(a, b, c) * cross(a, b, d) < 0.0 && cross(c, d, a) * cross(c, d, b) < 0.0
+}
+
+fn cross(a: Vec2, b: Vec2, c: Vec2) -> f32 {
+    (b - a).perp_dot(c - a)
+}
+
+fn is_ear(points: &[Vec2], remaining: &[u32], prev: u32, current: u32, next: u32) -> bool {
+    let [a, b, c] = [prev, current, next].map(|idx| points[idx as usize]);
+    if cross(a, b, c) <= 0.0 {
+        return false;
+    }
+
+    remaining.iter().all(|&idx| {
+        let point = points[idx as usize];
+        // vertices coinciding with a corner do not block the ear
+        if [prev, current, next].contains(&idx) || point == a || point == b || point == c {
+            return true;
+        }
+        !(cross(a, b, point) >= 0.0 && cross(b, c, point) >= 0.0 && cross(c, a, point) >= 0.0)
+    })
+}
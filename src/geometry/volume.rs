@@ -0,0 +1,392 @@
+use bevy::{
+    math::DVec3,
+    prelude::{Mesh, Vec2, Vec3},
+};
+
+use crate::geometry;
+
+/// Most samples a volume or heightfield grid is allowed to have, which is 256 along every axis of a cube.
+pub const MAX_SAMPLES: usize = 1 << 24;
+
+/// Signed distances sampled on a regular grid, negative inside a shape and positive outside of it. Samples are stored
+/// with X varying fastest, then Y, then Z.
+#[derive(Clone)]
+pub struct Volume {
+    /// Position of the first sample, the minimum corner of the grid.
+    pub origin: Vec3,
+    /// Distance between neighbouring samples.
+    pub spacing: f32,
+    /// Sample counts along X, Y and Z.
+    pub dims: [usize; 3],
+    pub values: Vec<f32>,
+}
+
+impl Volume {
+    /// Grid covering the box from `min` to `max` with at least two samples along every axis, all set to `value`. The
+    /// spacing grows as needed to keep the grid within [`MAX_SAMPLES`] samples.
+    pub fn new(min: Vec3, max: Vec3, spacing: f32, value: f32) -> Self {
+        let mut spacing = spacing.max(1e-4);
+        let mut counts = ((max - min) / spacing).ceil().max(Vec3::ONE) + Vec3::ONE;
+        loop {
+            let total = counts.to_array().iter().map(|&count| count as f64).product::<f64>();
+            if total.is_nan() || total <= MAX_SAMPLES as f64 {
+                break;
+            }
+            spacing *= ((total / MAX_SAMPLES as f64).cbrt() as f32).max(1.01);
+            counts = ((max - min) / spacing).ceil().max(Vec3::ONE) + Vec3::ONE;
+        }
+        let dims = counts.to_array().map(|count| count as usize);
+        Self {
+            origin: min,
+            spacing,
+            dims,
+            values: vec![value; dims.iter().product()],
+        }
+    }
+
+    /// Grid covering the box from `min` to `max` with every sample set by a distance function.
+    pub fn from_fn(min: Vec3, max: Vec3, spacing: f32, distance: impl Fn(Vec3) -> f32) -> Self {
+        let mut volume = Self::new(min, max, spacing, 0.0);
+        for z in 0..volume.dims[2] {
+            for y in 0..volume.dims[1] {
+                for x in 0..volume.dims[0] {
+                    let idx = volume.index(x, y, z);
+                    volume.values[idx] = distance(volume.position(x, y, z));
+                }
+            }
+        }
+        volume
+    }
+
+    /// Signed distance to a closed triangle mesh, over its bounds grown by `padding`. Distances are exact close to
+    /// the surface and propagated from the nearest triangles elsewhere, and the sign comes from counting surface
+    /// crossings along X, so open meshes get an inside wherever their holes let the count go odd.
+    pub fn from_mesh(mesh: &Mesh, spacing: f32, padding: f32) -> Option<Self> {
+        let triangles = geometry::triangles(mesh);
+        if triangles.is_empty() {
+            return None;
+        }
+        let positions = geometry::positions(mesh);
+        let (min, max) = geometry::aabb(&positions);
+        let mut volume = Self::new(
+            min - Vec3::splat(padding),
+            max + Vec3::splat(padding),
+            spacing,
+            f32::MAX,
+        );
+        let points = positions.iter().map(|point| point.as_dvec3()).collect::<Vec<_>>();
+        let grid_points = points
+            .iter()
+            .map(|point| (*point - volume.origin.as_dvec3()) / volume.spacing as f64)
+            .collect::<Vec<_>>();
+        let distance = |point: Vec3, tri_idx: usize| {
+            let [a, b, c] = triangles[tri_idx].map(|idx| points[idx as usize]);
+            let point = point.as_dvec3();
+            geometry::closest_point_on_triangle(point, a, b, c).distance(point) as f32
+        };
+
+        // exact distances in a band of one sample around every triangle
+        let mut nearest = vec![usize::MAX; volume.len()];
+        for (tri_idx, triangle) in triangles.iter().enumerate() {
+            let [a, b, c] = triangle.map(|idx| grid_points[idx as usize]);
+            let lower = (a.min(b).min(c).floor() - DVec3::ONE).max(DVec3::ZERO);
+            let upper = a.max(b).max(c).ceil() + DVec3::ONE;
+            let range = |axis: usize| {
+                let last = volume.dims[axis] - 1;
+                (lower[axis] as usize).min(last)..=(upper[axis] as usize).min(last)
+            };
+            for z in range(2) {
+                for y in range(1) {
+                    for x in range(0) {
+                        let idx = volume.index(x, y, z);
+                        let value = distance(volume.position(x, y, z), tri_idx);
+                        if value < volume.values[idx] {
+                            volume.values[idx] = value;
+                            nearest[idx] = tri_idx;
+                        }
+                    }
+                }
+            }
+        }
+
+        // the nearest triangles spread through the rest of the grid in sweeps along all eight diagonal directions
+        for _ in 0..2 {
+            for direction in 0..8 {
+                let steps = [0, 1, 2].map(|axis| if direction >> axis & 1 == 0 { 1 } else { -1 });
+                volume.sweep(steps, &mut nearest, &distance);
+            }
+        }
+
+        let mut crossings = vec![0u32; volume.len()];
+        for triangle in &triangles {
+            let [a, b, c] = triangle.map(|idx| grid_points[idx as usize]);
+            let lower = a.min(b).min(c).ceil().max(DVec3::ZERO);
+            let upper = a.max(b).max(c).floor();
+            if upper.y < 0.0 || upper.z < 0.0 {
+                continue;
+            }
+            for z in lower.z as usize..=(upper.z as usize).min(volume.dims[2] - 1) {
+                for y in lower.y as usize..=(upper.y as usize).min(volume.dims[1] - 1) {
+                    let weights = match barycentric_2d([y as f64, z as f64], [a.y, a.z], [b.y, b.z], [c.y, c.z]) {
+                        Some(weights) => weights,
+                        None => continue,
+                    };
+                    let x = (weights[0] * a.x + weights[1] * b.x + weights[2] * c.x).ceil().max(0.0) as usize;
+                    if x < volume.dims[0] {
+                        crossings[volume.index(x, y, z)] += 1;
+                    }
+                }
+            }
+        }
+        for z in 0..volume.dims[2] {
+            for y in 0..volume.dims[1] {
+                let mut total = 0;
+                for x in 0..volume.dims[0] {
+                    let idx = volume.index(x, y, z);
+                    total += crossings[idx];
+                    if total % 2 == 1 {
+                        volume.values[idx] = -volume.values[idx];
+                    }
+                }
+            }
+        }
+        Some(volume)
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        (z * self.dims[1] + y) * self.dims[0] + x
+    }
+
+    pub fn position(&self, x: usize, y: usize, z: usize) -> Vec3 {
+        self.origin + Vec3::new(x as f32, y as f32, z as f32) * self.spacing
+    }
+
+    pub fn value(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[self.index(x, y, z)]
+    }
+
+    /// Position of the last sample, the maximum corner of the grid.
+    pub fn max_corner(&self) -> Vec3 {
+        self.position(self.dims[0] - 1, self.dims[1] - 1, self.dims[2] - 1)
+    }
+
+    /// Trilinearly interpolated distance at any point. Outside of the grid the distance at the nearest point of its
+    /// bounds grows by how far away the point is.
+    pub fn sample(&self, point: Vec3) -> f32 {
+        let clamped = point.clamp(self.origin, self.max_corner());
+        let grid = (clamped - self.origin) / self.spacing;
+        let [x, y, z] = [0, 1, 2].map(|axis| (grid[axis] as usize).min(self.dims[axis] - 2));
+        let t = grid - Vec3::new(x as f32, y as f32, z as f32);
+
+        let mut value = 0.0;
+        for corner in 0..8 {
+            let [dx, dy, dz] = [0, 1, 2].map(|axis| corner >> axis & 1);
+            let weight = [dx, dy, dz]
+                .iter()
+                .enumerate()
+                .map(|(axis, &offset)| if offset == 1 { t[axis] } else { 1.0 - t[axis] })
+                .product::<f32>();
+            value += weight * self.value(x + dx, y + dy, z + dz);
+        }
+        value + point.distance(clamped)
+    }
+
+    /// Direction in which the distance grows fastest, pointing out of the shape.
+    pub fn gradient(&self, point: Vec3) -> Vec3 {
+        let step = self.spacing / 2.0;
+        let along = |axis: Vec3| (self.sample(point + axis * step) - self.sample(point - axis * step)) / (2.0 * step);
+        Vec3::new(along(Vec3::X), along(Vec3::Y), along(Vec3::Z))
+    }
+
+    /// Combination of this volume with another, resampled onto a grid with the finer spacing of the two, coarsened
+    /// like any grid that would go over [`MAX_SAMPLES`]. The grid covers both volumes for a union and this volume
+    /// otherwise. A `smoothness` above 0 blends the shapes into each
+    /// other over about that distance.
+    pub fn combine(&self, other: &Volume, operation: VolumeOperation, smoothness: f32) -> Volume {
+        let (mut min, mut max) = (self.origin, self.max_corner());
+        if operation == VolumeOperation::Union {
+            min = min.min(other.origin);
+            max = max.max(other.max_corner());
+        }
+        Self::from_fn(min, max, self.spacing.min(other.spacing), |point| {
+            let (a, b) = (self.sample(point), other.sample(point));
+            match operation {
+                VolumeOperation::Union => smooth_min(a, b, smoothness),
+                VolumeOperation::Subtract => -smooth_min(-a, b, smoothness),
+                VolumeOperation::Intersect => -smooth_min(-a, -b, smoothness),
+            }
+        })
+    }
+
+    /// One pass over the grid in the direction of `steps`, taking over the nearest triangle of already visited
+    /// neighbours when it is closer than the current one.
+    fn sweep(&mut self, steps: [isize; 3], nearest: &mut [usize], distance: &impl Fn(Vec3, usize) -> f32) {
+        let order = |axis: usize| {
+            let count = self.dims[axis];
+            let mut order = (0..count).collect::<Vec<_>>();
+            if steps[axis] < 0 {
+                order.reverse();
+            }
+            order
+        };
+        let (xs, ys, zs) = (order(0), order(1), order(2));
+        for &z in &zs {
+            for &y in &ys {
+                for &x in &xs {
+                    let idx = self.index(x, y, z);
+                    for offsets in 1..8 {
+                        let neighbour = [0, 1, 2].map(|axis| {
+                            let offset = if offsets >> axis & 1 == 1 { steps[axis] } else { 0 };
+                            [x, y, z][axis] as isize - offset
+                        });
+                        if (0..3).any(|axis| neighbour[axis] < 0 || neighbour[axis] >= self.dims[axis] as isize) {
+                            continue;
+                        }
+                        let [nx, ny, nz] = neighbour.map(|coordinate| coordinate as usize);
+                        let tri_idx = nearest[self.index(nx, ny, nz)];
+                        if tri_idx == usize::MAX || tri_idx == nearest[idx] {
+                            continue;
+                        }
+                        let value = distance(self.position(x, y, z), tri_idx);
+                        if value < self.values[idx] {
+                            self.values[idx] = value;
+                            nearest[idx] = tri_idx;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VolumeOperation {
+    Union,
+    /// Cuts the second shape out of the first.
+    Subtract,
+    Intersect,
+}
+
+/// Polynomial smooth minimum, which rounds off the crease where `a` and `b` meet over about `smoothness`.
+pub fn smooth_min(a: f32, b: f32, smoothness: f32) -> f32 {
+    if smoothness <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / smoothness).clamp(0.0, 1.0);
+    b + (a - b) * h - smoothness * h * (1.0 - h)
+}
+
+/// Shapes with an exact signed distance, centered on the origin.
+#[derive(Copy, Clone, Debug)]
+pub enum SdfPrimitive {
+    Sphere {
+        radius: f32,
+    },
+    /// Box with its edges rounded off by `radius`, which grows it beyond its half extents.
+    Box {
+        half_extents: Vec3,
+        radius: f32,
+    },
+    /// Ring around the Y axis.
+    Torus {
+        major_radius: f32,
+        minor_radius: f32,
+    },
+    /// Cylinder along the Y axis with hemispheres on its ends.
+    Capsule {
+        half_height: f32,
+        radius: f32,
+    },
+    /// Cylinder along the Y axis.
+    Cylinder {
+        half_height: f32,
+        radius: f32,
+    },
+}
+
+impl SdfPrimitive {
+    pub fn distance(&self, point: Vec3) -> f32 {
+        match *self {
+            SdfPrimitive::Sphere { radius } => point.length() - radius,
+            SdfPrimitive::Box { half_extents, radius } => {
+                let q = point.abs() - half_extents;
+                q.max(Vec3::ZERO).length() + q.max_element().min(0.0) - radius
+            },
+            SdfPrimitive::Torus {
+                major_radius,
+                minor_radius,
+            } => Vec2::new(Vec2::new(point.x, point.z).length() - major_radius, point.y).length() - minor_radius,
+            SdfPrimitive::Capsule { half_height, radius } => {
+                (point - Vec3::Y * point.y.clamp(-half_height, half_height)).length() - radius
+            },
+            SdfPrimitive::Cylinder { half_height, radius } => {
+                let q = Vec2::new(
+                    Vec2::new(point.x, point.z).length() - radius,
+                    point.y.abs() - half_height,
+                );
+                q.max(Vec2::ZERO).length() + q.max_element().min(0.0)
+            },
+        }
+    }
+
+    /// Half extents of the box around the shape.
+    pub fn extent(&self) -> Vec3 {
+        match *self {
+            SdfPrimitive::Sphere { radius } => Vec3::splat(radius),
+            SdfPrimitive::Box { half_extents, radius } => half_extents + Vec3::splat(radius),
+            SdfPrimitive::Torus {
+                major_radius,
+                minor_radius,
+            } => Vec3::new(major_radius + minor_radius, minor_radius, major_radius + minor_radius),
+            SdfPrimitive::Capsule { half_height, radius } => Vec3::new(radius, half_height + radius, radius),
+            SdfPrimitive::Cylinder { half_height, radius } => Vec3::new(radius, half_height, radius),
+        }
+    }
+}
+
+/// Barycentric weights of `point` in the triangle `a`, `b`, `c`, or `None` when it lies outside. Points on an edge
+/// count for exactly one of the two triangles sharing it, so that crossings along a grid line are never doubled.
+fn barycentric_2d(point: [f64; 2], a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> Option<[f64; 3]> {
+    let [a, b, c] = [a, b, c].map(|corner| [corner[0] - point[0], corner[1] - point[1]]);
+    let (sign_a, area_a) = orientation(b, c);
+    let (sign_b, area_b) = orientation(c, a);
+    let (sign_c, area_c) = orientation(a, b);
+    if sign_a != sign_b || sign_b != sign_c {
+        return None;
+    }
+    let total = area_a + area_b + area_c;
+    if total == 0.0 {
+        return None;
+    }
+    Some([area_a / total, area_b / total, area_c / total])
+}
+
+/// Side of the origin the directed segment from `a` to `b` passes on, along with twice the signed area of the
+/// triangle they form with it. Ties are broken by the coordinates so that the result is never 0 for a proper segment.
+fn orientation(a: [f64; 2], b: [f64; 2]) -> (i8, f64) {
+    let area = a[1] * b[0] - a[0] * b[1];
+    let sign = if area > 0.0 {
+        1
+    } else if area < 0.0 {
+        -1
+    } else if b[1] > a[1] {
+        1
+    } else if b[1] < a[1] {
+        -1
+    } else if a[0] > b[0] {
+        1
+    } else if a[0] < b[0] {
+        -1
+    } else {
+        0
+    };
+    (sign, area)
+}
@@ -0,0 +1,142 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::{
+    prelude::{Mesh, Vec3},
+    render::mesh::{PrimitiveTopology, VertexAttributeValues},
+};
+
+use crate::geometry::{self, SpatialHash};
+
+/// Cross products shorter than this are treated as zero-area triangles.
+const DEGENERATE_EPSILON: f32 = 1e-12;
+
+/// Merges vertices closer than `distance` into one and drops the primitives that collapse or repeat as a result.
+///
+/// Returns the welded mesh together with the new index of every original vertex. With `average` set the float
+/// attributes of a merged vertex are the mean of its sources, otherwise the first source vertex is kept. A distance
+/// of 0 or less only merges vertices at exactly the same position.
+pub fn weld(mesh: &Mesh, distance: f32, average: bool) -> (Mesh, Vec<u32>) {
+    let positions = geometry::positions(mesh);
+    let mut hash = SpatialHash::new(distance);
+    let mut exact = HashMap::new();
+    let mut clusters: Vec<Vec<u32>> = Vec::new();
+    let mut remap = Vec::with_capacity(positions.len());
+
+    for (idx, position) in positions.iter().enumerate() {
+        // adding zero turns -0.0 into 0.0 so that both land on the same key
+        let key = position.to_array().map(|coordinate| (coordinate + 0.0).to_bits());
+        let existing = if distance > 0.0 {
+            hash.neighbors(*position)
+                .find(|&cluster| positions[clusters[cluster as usize][0] as usize].distance(*position) <= distance)
+        } else {
+            exact.get(&key).copied()
+        };
+
+        match existing {
+            Some(cluster) => {
+                clusters[cluster as usize].push(idx as u32);
+                remap.push(cluster);
+            },
+            None => {
+                let cluster = clusters.len() as u32;
+                if distance > 0.0 {
+                    hash.insert(cluster, *position);
+                } else {
+                    exact.insert(key, cluster);
+                }
+                clusters.push(vec![idx as u32]);
+                remap.push(cluster);
+            },
+        }
+    }
+
+    let firsts = clusters.iter().map(|cluster| cluster[0]).collect::<Vec<_>>();
+    let mut result = geometry::gather(mesh, &firsts);
+
+    if average {
+        for (id, values) in result.attributes_mut() {
+            if let Some(averaged) = mesh
+                .attribute(id)
+                .and_then(|original| average_values(original, &clusters))
+            {
+                *values = averaged;
+            }
+        }
+        if let Some(VertexAttributeValues::Float32x3(normals)) = result.attribute_mut(Mesh::ATTRIBUTE_NORMAL) {
+            for normal in normals.iter_mut() {
+                *normal = Vec3::from(*normal).normalize_or_zero().to_array();
+            }
+        }
+    }
+
+    let welded_positions = geometry::positions(&result);
+    let indices = geometry::indices(mesh)
+        .into_iter()
+        .map(|idx| remap[idx as usize])
+        .collect::<Vec<_>>();
+
+    match mesh.primitive_topology() {
+        PrimitiveTopology::PointList => {},
+        PrimitiveTopology::LineList => {
+            let mut seen = HashSet::new();
+            let lines = indices
+                .chunks_exact(2)
+                .filter(|line| line[0] != line[1] && seen.insert((line[0].min(line[1]), line[0].max(line[1]))))
+                .flatten()
+                .copied()
+                .collect();
+            geometry::set_indices(&mut result, lines);
+        },
+        PrimitiveTopology::TriangleList => {
+            let mut seen = HashSet::new();
+            let triangles = indices
+                .chunks_exact(3)
+                .filter(|tri| {
+                    let [a, b, c] = [tri[0], tri[1], tri[2]].map(|idx| welded_positions[idx as usize]);
+                    let mut key = [tri[0], tri[1], tri[2]];
+                    key.sort_unstable();
+                    key[0] != key[1]
+                        && key[1] != key[2]
+                        && (b - a).cross(c - a).length_squared() > DEGENERATE_EPSILON
+                        && seen.insert(key)
+                })
+                .flatten()
+                .copied()
+                .collect();
+            geometry::set_indices(&mut result, triangles);
+        },
+        _ => geometry::set_indices(&mut result, indices),
+    }
+
+    (result, remap)
+}
+
+fn average_values(values: &VertexAttributeValues, clusters: &[Vec<u32>]) -> Option<VertexAttributeValues> {
+    fn mean<const N: usize>(values: &[[f32; N]], clusters: &[Vec<u32>]) -> Vec<[f32; N]> {
+        clusters
+            .iter()
+            .map(|cluster| {
+                let mut sum = [0.0; N];
+                for &idx in cluster {
+                    for (total, value) in sum.iter_mut().zip(values[idx as usize]) {
+                        *total += value;
+                    }
+                }
+                sum.map(|total| total / cluster.len() as f32)
+            })
+            .collect()
+    }
+
+    match values {
+        VertexAttributeValues::Float32(values) => Some(VertexAttributeValues::Float32(
+            clusters
+                .iter()
+                .map(|cluster| cluster.iter().map(|&idx| values[idx as usize]).sum::<f32>() / cluster.len() as f32)
+                .collect(),
+        )),
+        VertexAttributeValues::Float32x2(values) => Some(VertexAttributeValues::Float32x2(mean(values, clusters))),
+        VertexAttributeValues::Float32x3(values) => Some(VertexAttributeValues::Float32x3(mean(values, clusters))),
+        VertexAttributeValues::Float32x4(values) => Some(VertexAttributeValues::Float32x4(mean(values, clusters))),
+        _ => None,
+    }
+}
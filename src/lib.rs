@@ -7,8 +7,11 @@ pub use self::{
 };
 
 pub mod entity;
+pub mod geometry;
 pub mod node;
+pub mod noise;
 pub mod process;
+pub mod svg;
 
 pub struct CopperPlugin;
 
@@ -16,6 +19,8 @@ impl Plugin for CopperPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<node::UpdateEvent>()
             .add_system(process::finalize)
-            .add_system(node::final_update);
+            .add_system(node::final_update)
+            .add_system(node::final_lines_update)
+            .add_system(node::lod_update);
     }
 }
@@ -2,13 +2,61 @@ use std::any::Any;
 
 use bevy::prelude::{BuildChildren, Commands, Component, Entity};
 
-pub use self::{material::*, r#box::*, r#final::*, selection_group::*};
-use crate::{store_entity, ProcessObject};
+pub use self::{
+    array::*, bend::*, bezier::*, bound::*, circle::*, connectivity::*, for_each::*, fuse::*, heightfield_convert::*,
+    heightfield_create::*, heightfield_erode::*, heightfield_layer::*, heightfield_mask::*, heightfield_noise::*,
+    l_system::*, lattice::*, line::*, lod::*, loft::*, material::*, noise::*, nurbs::*, poly_reduce::*, promoted::*,
+    r#box::*, r#final::*, remesh::*, resample::*, revolve::*, scatter::*, selection_group::*, smooth::*, subnet::*,
+    svg_import::*, sweep::*, switch::*, taper::*, text::*, triangulate::*, twist::*, volume_combine::*,
+    volume_convert::*, volume_from_mesh::*, volume_primitive::*, voronoi_fracture::*,
+};
+use crate::{store_entity, CookContext, ProcessObject};
 
+pub mod array;
+pub mod bend;
+pub mod bezier;
+pub mod bound;
 pub mod r#box;
+pub mod circle;
+pub mod connectivity;
 pub mod r#final;
+pub mod for_each;
+pub mod fuse;
+pub mod heightfield_convert;
+pub mod heightfield_create;
+pub mod heightfield_erode;
+pub mod heightfield_layer;
+pub mod heightfield_mask;
+pub mod heightfield_noise;
+pub mod l_system;
+pub mod lattice;
+pub mod line;
+pub mod lod;
+pub mod loft;
 pub mod material;
+pub mod noise;
+pub mod nurbs;
+pub mod poly_reduce;
+pub mod promoted;
+pub mod remesh;
+pub mod resample;
+pub mod revolve;
+pub mod scatter;
 pub mod selection_group;
+pub mod smooth;
+pub mod subnet;
+pub mod svg_import;
+pub mod sweep;
+pub mod switch;
+pub mod taper;
+pub mod text;
+pub mod triangulate;
+pub mod twist;
+pub mod volume_combine;
+pub mod volume_convert;
+pub mod volume_from_mesh;
+pub mod volume_primitive;
+pub mod voronoi_fracture;
 
 #[derive(Copy, Clone)]
 pub struct SpawnedNode {
@@ -29,6 +77,19 @@ impl SpawnedNode {
 
 pub trait CommonNode: Send + Sync + Any {
     fn process(&self, object: &mut ProcessObject);
+
+    /// Positions of the input children, out of `count`, that are cooked before this node. All of them by default.
+    fn active_inputs(&self, count: usize) -> Vec<usize> {
+        (0..count).collect()
+    }
+
+    /// Cooks the node with access to the graph, after its active inputs, for nodes that evaluate other parts of the
+    /// graph themselves. `inputs` are the input children of the node. Returns false to fall back to
+    /// [`CommonNode::process`], which is what the default does.
+    fn cook(&self, _object: &mut ProcessObject, _inputs: &[Entity], _context: &mut CookContext) -> bool {
+        false
+    }
+
     fn as_any_ref(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
@@ -41,6 +102,14 @@ impl CommonNode for Node {
         self.0.process(object)
     }
 
+    fn active_inputs(&self, count: usize) -> Vec<usize> {
+        self.0.active_inputs(count)
+    }
+
+    fn cook(&self, object: &mut ProcessObject, inputs: &[Entity], context: &mut CookContext) -> bool {
+        self.0.cook(object, inputs, context)
+    }
+
     fn as_any_ref(&self) -> &dyn Any {
         self.0.as_any_ref()
     }
@@ -0,0 +1,143 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Mat4, Quat, Transform, Vec3};
+
+use crate::{
+    geometry::{self, ATTRIBUTE_COPYNUM},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct ArrayType;
+
+impl TypedNode for Array {
+    type Type = ArrayType;
+}
+
+pub enum ArrayKind {
+    /// Every copy is offset from the previous one by the transform.
+    Linear(Transform),
+    /// Every copy is rotated from the previous one by `angle` radians around `axis` passing through `center`.
+    Radial { center: Vec3, axis: Vec3, angle: f32 },
+}
+
+impl ArrayKind {
+    pub fn step(&self) -> Mat4 {
+        match self {
+            Self::Linear(transform) => transform.compute_matrix(),
+            Self::Radial { center, axis, angle } => {
+                Mat4::from_translation(*center)
+                    * Mat4::from_quat(Quat::from_axis_angle(axis.normalize_or_zero(), *angle))
+                    * Mat4::from_translation(-*center)
+            },
+        }
+    }
+}
+
+pub struct Array {
+    pub count: u32,
+    pub kind: ArrayKind,
+}
+
+impl Array {
+    pub fn new(count: u32, transform: Transform) -> Self {
+        Self {
+            count,
+            kind: ArrayKind::Linear(transform),
+        }
+    }
+
+    pub fn radial(count: u32, center: Vec3, axis: Vec3, angle: f32) -> Self {
+        Self {
+            count,
+            kind: ArrayKind::Radial { center, axis, angle },
+        }
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((ArrayType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Array {
+    fn default() -> Self {
+        Self::new(2, Transform::from_xyz(1.0, 0.0, 0.0))
+    }
+}
+
+impl CommonNode for Array {
+    fn process(&self, object: &mut ProcessObject) {
+        if self.count == 0 {
+            // no copies leave no geometry of any kind, only what describes the object around it
+            let ProcessObject {
+                materials,
+                parameters,
+                transform,
+                global_transform,
+                ..
+            } = std::mem::take(object);
+            *object = ProcessObject {
+                materials,
+                parameters,
+                transform,
+                global_transform,
+                ..Default::default()
+            };
+            return;
+        }
+
+        let step = self.kind.step();
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let vertex_count = mesh.count_vertices() as u32;
+            let mut matrix = Mat4::IDENTITY;
+            let mut copies = Vec::with_capacity(self.count as usize);
+
+            for copynum in 0..self.count {
+                let mut copy = mesh.clone();
+                geometry::transform(&mut copy, matrix);
+                copy.insert_attribute(ATTRIBUTE_COPYNUM, vec![copynum; vertex_count as usize]);
+                copies.push(copy);
+                matrix *= step;
+            }
+
+            match geometry::merge(&copies) {
+                Some(merged) => *mesh = merged,
+                None => continue,
+            }
+
+            for selection in object.selections.values_mut().flatten() {
+                if selection.mesh == idx {
+                    let indices = std::mem::take(&mut selection.indices);
+                    selection.indices = (0..self.count)
+                        .flat_map(|copynum| indices.iter().map(move |vertex| vertex + copynum * vertex_count))
+                        .collect();
+                }
+            }
+
+            if let Some(polygons) = object.polygons.get_mut(&idx) {
+                let faces = std::mem::take(polygons);
+                *polygons = (0..self.count)
+                    .flat_map(|copynum| {
+                        faces.iter().map(move |face| {
+                            face.iter()
+                                .map(|vertex| vertex + copynum * vertex_count)
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
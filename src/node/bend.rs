@@ -0,0 +1,101 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{self, Capture},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct BendType;
+
+impl TypedNode for Bend {
+    type Type = BendType;
+}
+
+/// Curls the capture region into a circular arc of `angle` radians towards `up`; geometry past the end of the region
+/// follows rigidly along the final tangent.
+pub struct Bend {
+    pub capture: Capture,
+    pub up: Vec3,
+    pub angle: f32,
+    pub group: Option<String>,
+}
+
+impl Bend {
+    pub fn new(capture: Capture, angle: f32) -> Self {
+        Self {
+            capture,
+            up: Vec3::X,
+            angle,
+            group: None,
+        }
+    }
+
+    pub fn with_up(mut self, up: Vec3) -> Self {
+        self.up = up;
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((BendType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn deform(&self, position: Vec3) -> Vec3 {
+        let axis = self.capture.axis();
+        let up = (self.up - axis * self.up.dot(axis)).normalize_or_zero();
+        if self.angle.abs() <= f32::EPSILON || self.capture.length <= 0.0 || up == Vec3::ZERO {
+            return position;
+        }
+
+        let local = position - self.capture.origin;
+        let along = local.dot(axis);
+        if along <= 0.0 {
+            return position;
+        }
+
+        let across = local.dot(up);
+        let rest = local - axis * along - up * across;
+        let radius = self.capture.length / self.angle;
+        let bent = along.min(self.capture.length);
+        let phi = self.angle * bent / self.capture.length;
+        let arm = radius - across;
+        let tangent = axis * phi.cos() + up * phi.sin();
+
+        self.capture.origin
+            + up * (radius - arm * phi.cos())
+            + axis * (arm * phi.sin())
+            + tangent * (along - bent)
+            + rest
+    }
+}
+
+impl Default for Bend {
+    fn default() -> Self {
+        Self::new(Capture::default(), std::f32::consts::FRAC_PI_2)
+    }
+}
+
+impl CommonNode for Bend {
+    fn process(&self, object: &mut ProcessObject) {
+        geometry::deform_selected(object, self.group.as_deref(), |position| self.deform(position));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,69 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{geometry::Spline, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct BezierType;
+
+impl TypedNode for Bezier {
+    type Type = BezierType;
+}
+
+/// Adds a chain of Bezier segments of `degree`, passing through every `degree`-th control point. The curve is a
+/// spline until it goes through `Resample`.
+pub struct Bezier {
+    pub points: Vec<Vec3>,
+    pub degree: u32,
+}
+
+impl Bezier {
+    pub fn new(points: impl Into<Vec<Vec3>>) -> Self {
+        Self {
+            points: points.into(),
+            degree: 3,
+        }
+    }
+
+    pub fn with_degree(mut self, degree: u32) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((BezierType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Bezier {
+    fn default() -> Self {
+        Self::new([
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(-0.5, 1.0, 0.0),
+            Vec3::new(0.5, 1.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ])
+    }
+}
+
+impl CommonNode for Bezier {
+    fn process(&self, object: &mut ProcessObject) {
+        if self.points.len() > 1 {
+            object
+                .splines
+                .push(Spline::bezier(self.points.clone(), self.degree as usize));
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
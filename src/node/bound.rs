@@ -0,0 +1,159 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::{
+    prelude::{shape, Commands, Component, Mat4, Mesh, Vec3},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct BoundType;
+
+impl TypedNode for Bound {
+    type Type = BoundType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum BoundKind {
+    /// Box along the mesh axes.
+    Aabb,
+    /// Box along the principal axes of the points.
+    OrientedBox,
+    Sphere,
+    /// Convex hull, falling back to the axis-aligned box for flat geometry.
+    ConvexHull,
+}
+
+/// Replaces every mesh with a simplified envelope of its points, optionally one per connected piece. Selection
+/// groups and faces of the replaced meshes are dropped.
+pub struct Bound {
+    pub kind: BoundKind,
+    pub per_piece: bool,
+}
+
+impl Bound {
+    pub fn new(kind: BoundKind) -> Self {
+        Self { kind, per_piece: false }
+    }
+
+    pub fn with_per_piece(mut self, per_piece: bool) -> Self {
+        self.per_piece = per_piece;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((BoundType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn envelope(&self, points: &[Vec3]) -> Mesh {
+        match self.kind {
+            BoundKind::Aabb => aabb_mesh(points),
+            BoundKind::OrientedBox => {
+                let (center, axes, half_extents) = geometry::oriented_box(points);
+                let size = half_extents * 2.0;
+                let mut mesh = Mesh::from(shape::Box::new(size.x, size.y, size.z));
+                geometry::transform(&mut mesh, Mat4::from_translation(center) * Mat4::from_mat3(axes));
+                mesh
+            },
+            BoundKind::Sphere => {
+                let (center, radius) = geometry::bounding_sphere(points);
+                let mut mesh = Mesh::from(shape::UVSphere {
+                    radius,
+                    sectors: 32,
+                    stacks: 16,
+                });
+                geometry::transform(&mut mesh, Mat4::from_translation(center));
+                mesh
+            },
+            BoundKind::ConvexHull => {
+                let hull = geometry::convex_hull(points);
+                if hull.is_empty() {
+                    return aabb_mesh(points);
+                }
+
+                let positions = hull
+                    .iter()
+                    .flatten()
+                    .map(|&idx| points[idx as usize].to_array())
+                    .collect::<Vec<_>>();
+                let normals = hull
+                    .iter()
+                    .flat_map(|tri| {
+                        let [a, b, c] = tri.map(|idx| points[idx as usize]);
+                        [geometry::triangle_normal(a, b, c).to_array(); 3]
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+                geometry::set_indices(&mut mesh, (0..positions.len() as u32).collect());
+                mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+                mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+                mesh
+            },
+        }
+    }
+}
+
+fn aabb_mesh(points: &[Vec3]) -> Mesh {
+    let (min, max) = geometry::aabb(points);
+    Mesh::from(shape::Box {
+        min_x: min.x,
+        max_x: max.x,
+        min_y: min.y,
+        max_y: max.y,
+        min_z: min.z,
+        max_z: max.z,
+    })
+}
+
+impl Default for Bound {
+    fn default() -> Self {
+        Self::new(BoundKind::Aabb)
+    }
+}
+
+impl CommonNode for Bound {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut replaced = HashSet::new();
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let positions = geometry::positions(mesh);
+            if positions.is_empty() {
+                continue;
+            }
+
+            let envelopes = if self.per_piece {
+                let (pieces, count) = geometry::connected_pieces(mesh);
+                let mut piece_points = vec![Vec::new(); count as usize];
+                for (position, piece) in positions.iter().zip(pieces) {
+                    piece_points[piece as usize].push(*position);
+                }
+                piece_points.iter().map(|points| self.envelope(points)).collect()
+            } else {
+                vec![self.envelope(&positions)]
+            };
+
+            if let Some(merged) = geometry::merge(&envelopes) {
+                *mesh = merged;
+                replaced.insert(idx);
+            }
+        }
+
+        for selections in object.selections.values_mut() {
+            selections.retain(|selection| !replaced.contains(&selection.mesh));
+        }
+        object.selections.retain(|_, selections| !selections.is_empty());
+        object.polygons.retain(|mesh, _| !replaced.contains(mesh));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
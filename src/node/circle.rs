@@ -0,0 +1,115 @@
+use std::{any::Any, f32::consts::TAU};
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{self, Surface},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct CircleType;
+
+impl TypedNode for Circle {
+    type Type = CircleType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum CircleKind {
+    /// Curve that stays open, so arcs keep a gap between their ends. A full turn ends on a copy of its first point.
+    Open,
+    /// Closed curve; arcs are closed by the chord between their ends.
+    Closed,
+    /// Single polygon face bounded by the closed curve.
+    Filled,
+}
+
+/// Adds a circle or an arc from `start_angle` to `end_angle` around `center` in the XY plane, counter-clockwise as
+/// seen from +Z, with `divisions` segments along the arc. Filled circles face +Z.
+pub struct Circle {
+    pub center: Vec3,
+    pub radius: f32,
+    pub divisions: u32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub kind: CircleKind,
+}
+
+impl Circle {
+    pub fn new(radius: f32, divisions: u32) -> Self {
+        Self {
+            center: Vec3::ZERO,
+            radius,
+            divisions,
+            start_angle: 0.0,
+            end_angle: TAU,
+            kind: CircleKind::Closed,
+        }
+    }
+
+    pub fn with_center(mut self, center: Vec3) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_arc(mut self, start_angle: f32, end_angle: f32) -> Self {
+        self.start_angle = start_angle;
+        self.end_angle = end_angle;
+        self
+    }
+
+    pub fn with_kind(mut self, kind: CircleKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((CircleType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn points(&self) -> Vec<Vec3> {
+        let sweep = self.end_angle - self.start_angle;
+        let divisions = self.divisions.max(3);
+        // a full turn ends on its first point, which only an open curve needs to draw its last segment
+        let count = if sweep.abs() >= TAU - 1e-4 && self.kind != CircleKind::Open {
+            divisions
+        } else {
+            divisions + 1
+        };
+        (0..count)
+            .map(|idx| {
+                let angle = self.start_angle + sweep * idx as f32 / divisions as f32;
+                self.center + Vec3::new(angle.cos(), angle.sin(), 0.0) * self.radius
+            })
+            .collect()
+    }
+}
+
+impl Default for Circle {
+    fn default() -> Self {
+        Self::new(1.0, 12)
+    }
+}
+
+impl CommonNode for Circle {
+    fn process(&self, object: &mut ProcessObject) {
+        let points = self.points();
+        match self.kind {
+            CircleKind::Open => object.meshes.push(geometry::polyline(&points, false)),
+            CircleKind::Closed => object.meshes.push(geometry::polyline(&points, true)),
+            CircleKind::Filled => object.push_surface(Surface::cap(&points, Vec3::Z)),
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,75 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{
+    geometry::{self, ATTRIBUTE_CLASS},
+    node::{Finals, Selection},
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct ConnectivityType;
+
+impl TypedNode for Connectivity {
+    type Type = ConnectivityType;
+}
+
+/// Labels every vertex with the connected piece it belongs to in [`ATTRIBUTE_CLASS`], numbering pieces per mesh.
+/// Primitives belong to the piece of their vertices.
+///
+/// With a group prefix, each piece also becomes a selection group named by the prefix followed by its class.
+#[derive(Default)]
+pub struct Connectivity {
+    pub group_prefix: Option<String>,
+}
+
+impl Connectivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_groups(mut self, prefix: impl Into<String>) -> Self {
+        self.group_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((ConnectivityType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Connectivity {
+    fn process(&self, object: &mut ProcessObject) {
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let (pieces, count) = geometry::connected_pieces(mesh);
+
+            if let Some(prefix) = &self.group_prefix {
+                let mut piece_vertices = vec![Vec::new(); count as usize];
+                for (vertex, &piece) in pieces.iter().enumerate() {
+                    piece_vertices[piece as usize].push(vertex as u32);
+                }
+                for (piece, indices) in piece_vertices.into_iter().enumerate() {
+                    object
+                        .selections
+                        .entry(format!("{}{}", prefix, piece))
+                        .or_default()
+                        .push(Selection { mesh: idx, indices });
+                }
+            }
+
+            mesh.insert_attribute(ATTRIBUTE_CLASS, pieces);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -1,8 +1,13 @@
 use std::collections::HashSet;
 
-use bevy::prelude::{Bundle, Commands, Component, Entity, EventReader, PbrBundle, Query, With};
+use bevy::prelude::{
+    Bundle, Changed, Commands, Component, Entity, EventReader, PbrBundle, Query, Transform, With, Without,
+};
 
-use crate::{PbrState, SpawnedNode, TypedNode};
+use crate::{
+    node::{LodLevel, Lods},
+    PbrState, SpawnedNode, TypedNode,
+};
 
 #[derive(Copy, Clone, Component)]
 pub struct FinalType;
@@ -13,6 +18,7 @@ pub struct Final {
     #[bundle]
     pub pbr: PbrBundle,
     pub pbr_state: PbrState,
+    pub lods: Lods,
 }
 
 impl Final {
@@ -21,9 +27,15 @@ impl Final {
             _type_marker: FinalType,
             pbr: Default::default(),
             pbr_state: Default::default(),
+            lods: Default::default(),
         }
     }
 
+    pub fn with_lods(mut self, levels: impl Into<Vec<LodLevel>>) -> Self {
+        self.lods = Lods(levels.into());
+        self
+    }
+
     pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
         let id = commands.spawn_bundle(self).id();
         SpawnedNode { id }
@@ -45,6 +57,24 @@ pub struct UpdateEvent(pub Entity);
 #[derive(Default, Component)]
 pub struct Finals(pub HashSet<Entity>);
 
+/// Entities drawing the line meshes of a `Final`, which cannot be its children since those are its inputs.
+#[derive(Default, Component)]
+pub struct FinalLines(pub Vec<Entity>);
+
+/// Keeps the lines of every final where the final is.
+pub fn final_lines_update(
+    finals: Query<(&FinalLines, &Transform), Changed<Transform>>,
+    mut lines: Query<&mut Transform, Without<FinalLines>>,
+) {
+    for (final_lines, transform) in finals.iter() {
+        for line in &final_lines.0 {
+            if let Ok(mut line_transform) = lines.get_mut(*line) {
+                *line_transform = *transform;
+            }
+        }
+    }
+}
+
 pub fn final_update(mut events: EventReader<UpdateEvent>, mut query: Query<&mut PbrState, With<FinalType>>) {
     for UpdateEvent(entity_id) in events.iter() {
         match query.get_mut(*entity_id) {
@@ -0,0 +1,180 @@
+use std::{any::Any, collections::HashMap};
+
+use bevy::{
+    prelude::{Commands, Component, Entity, Mesh},
+    render::mesh::MeshVertexAttributeId,
+};
+
+use crate::{
+    geometry::{self, ATTRIBUTE_COPYNUM},
+    node::{Finals, Selection},
+    CommonNode, CookContext, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct ForEachType;
+
+impl TypedNode for ForEach {
+    type Type = ForEachType;
+}
+
+#[derive(Copy, Clone)]
+pub enum ForEachMode {
+    /// Runs the body once per connected piece of every mesh.
+    Pieces,
+    /// Runs the body once per distinct value of a scalar attribute, such as `ATTRIBUTE_CLASS`.
+    Attribute(MeshVertexAttributeId),
+    /// Runs the body on the whole input `count` times, tagging every result with its iteration in
+    /// `ATTRIBUTE_COPYNUM`.
+    Count(u32),
+    /// Runs the body `count` times, each time on the result of the previous iteration, and keeps the last result.
+    Feedback(u32),
+}
+
+impl ForEachMode {
+    /// Takes the object apart into the inputs of every iteration, runs `body` on each of them and merges the results
+    /// back into the object.
+    pub fn run(&self, object: &mut ProcessObject, mut body: impl FnMut(&mut ProcessObject)) {
+        match *self {
+            Self::Feedback(count) => {
+                for _ in 0..count {
+                    body(object);
+                }
+            },
+            Self::Count(count) => {
+                let input = std::mem::take(object);
+                for iteration in 0..count {
+                    let mut result = input.clone();
+                    body(&mut result);
+                    for mesh in result.meshes.iter_mut() {
+                        let vertex_count = mesh.count_vertices();
+                        mesh.insert_attribute(ATTRIBUTE_COPYNUM, vec![iteration; vertex_count]);
+                    }
+                    object.merge(result);
+                }
+            },
+            Self::Pieces | Self::Attribute(_) => {
+                let input = std::mem::take(object);
+                for (idx, mesh) in input.meshes.iter().enumerate() {
+                    let (labels, count) = match *self {
+                        Self::Attribute(id) => {
+                            geometry::attribute_labels(mesh, id).unwrap_or_else(|| (vec![0; mesh.count_vertices()], 1))
+                        },
+                        _ => geometry::connected_pieces(mesh),
+                    };
+
+                    for (piece, vertices) in geometry::split_pieces(mesh, &labels, count) {
+                        let mut result = piece_object(&input, idx, piece, &vertices);
+                        body(&mut result);
+                        object.merge(result);
+                    }
+                }
+            },
+        }
+    }
+}
+
+/// Iteration input holding a single piece of mesh `idx`, with the selections and faces that fall on it.
+fn piece_object(input: &ProcessObject, idx: usize, piece: Mesh, vertices: &[u32]) -> ProcessObject {
+    let remap = vertices
+        .iter()
+        .enumerate()
+        .map(|(new_idx, &vertex)| (vertex, new_idx as u32))
+        .collect::<HashMap<_, _>>();
+
+    let mut object = ProcessObject {
+        materials: input.materials.clone(),
+        parameters: input.parameters.clone(),
+        transform: input.transform,
+        global_transform: input.global_transform,
+        ..Default::default()
+    };
+
+    for (group, selections) in &input.selections {
+        let indices = selections
+            .iter()
+            .filter(|selection| selection.mesh == idx)
+            .flat_map(|selection| selection.indices.iter().filter_map(|vertex| remap.get(vertex).copied()))
+            .collect::<Vec<_>>();
+        if !indices.is_empty() {
+            object
+                .selections
+                .insert(group.clone(), vec![Selection { mesh: 0, indices }]);
+        }
+    }
+
+    if let Some(faces) = input.polygons.get(&idx) {
+        let faces = faces
+            .iter()
+            .filter_map(|face| {
+                face.iter()
+                    .map(|vertex| remap.get(vertex).copied())
+                    .collect::<Option<Vec<_>>>()
+            })
+            .collect::<Vec<_>>();
+        // faces straddling pieces cannot be kept, and partial faces would not describe the whole piece
+        let face_triangles = faces.iter().map(|face| face.len() - 2).sum::<usize>();
+        if face_triangles == geometry::triangles(&piece).len() {
+            object.polygons.insert(0, faces);
+        }
+    }
+
+    object.meshes.push(piece);
+    object
+}
+
+/// Runs a subgraph per iteration and merges the results. The body is the last node of a chain that is not an input
+/// of this node; when cooking, the chain is evaluated on every iteration input instead of on an empty object.
+pub struct ForEach {
+    pub mode: ForEachMode,
+    pub body: Option<Entity>,
+}
+
+impl ForEach {
+    pub fn new(mode: ForEachMode) -> Self {
+        Self { mode, body: None }
+    }
+
+    pub fn with_body(mut self, body: Entity) -> Self {
+        self.body = Some(body);
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((ForEachType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for ForEach {
+    fn default() -> Self {
+        Self::new(ForEachMode::Pieces)
+    }
+}
+
+impl CommonNode for ForEach {
+    /// Without a body this only splits and merges the input again.
+    fn process(&self, object: &mut ProcessObject) {
+        self.mode.run(object, |_| {});
+    }
+
+    fn cook(&self, object: &mut ProcessObject, _inputs: &[Entity], context: &mut CookContext) -> bool {
+        match self.body {
+            Some(body) => {
+                self.mode.run(object, |iteration| context.cook(body, iteration));
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
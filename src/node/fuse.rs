@@ -0,0 +1,88 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct FuseType;
+
+impl TypedNode for Fuse {
+    type Type = FuseType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FuseAttributes {
+    /// Merged points take the mean of the float attributes of the points they replace.
+    Average,
+    /// Merged points keep the attributes of the first point of their cluster.
+    Keep,
+}
+
+pub struct Fuse {
+    pub distance: f32,
+    pub attributes: FuseAttributes,
+}
+
+impl Fuse {
+    pub fn new(distance: f32) -> Self {
+        Self {
+            distance,
+            attributes: FuseAttributes::Average,
+        }
+    }
+
+    pub fn with_attributes(mut self, attributes: FuseAttributes) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((FuseType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Fuse {
+    fn default() -> Self {
+        Self::new(0.001)
+    }
+}
+
+impl CommonNode for Fuse {
+    fn process(&self, object: &mut ProcessObject) {
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let (welded, remap) = geometry::weld(mesh, self.distance, self.attributes == FuseAttributes::Average);
+            *mesh = welded;
+
+            for selection in object.selections.values_mut().flatten() {
+                if selection.mesh == idx {
+                    selection.indices = selection.indices.iter().map(|&vertex| remap[vertex as usize]).collect();
+                    selection.indices.sort_unstable();
+                    selection.indices.dedup();
+                }
+            }
+
+            if let Some(polygons) = object.polygons.get_mut(&idx) {
+                for face in polygons.iter_mut() {
+                    face.iter_mut().for_each(|vertex| *vertex = remap[*vertex as usize]);
+                    face.dedup();
+                    if face.len() > 1 && face.first() == face.last() {
+                        face.pop();
+                    }
+                }
+                polygons.retain(|face| face.len() >= 3);
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,69 @@
+use std::any::Any;
+
+use bevy::{
+    prelude::{Commands, Component},
+    render::mesh::MeshVertexAttribute,
+};
+
+use crate::{geometry::Surface, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldConvertType;
+
+impl TypedNode for HeightfieldConvert {
+    type Type = HeightfieldConvertType;
+}
+
+/// Turns the heightfields into one grid mesh with a quad per cell, carrying normalized heights in
+/// `ATTRIBUTE_HEIGHT` and slopes in `ATTRIBUTE_SLOPE`. Mask layers listed in `masks` are copied into the paired
+/// `Float32` attributes, with 0 where a heightfield lacks the layer.
+#[derive(Default)]
+pub struct HeightfieldConvert {
+    pub masks: Vec<(String, MeshVertexAttribute)>,
+}
+
+impl HeightfieldConvert {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_mask(mut self, mask: impl Into<String>, attribute: MeshVertexAttribute) -> Self {
+        self.masks.push((mask.into(), attribute));
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldConvertType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for HeightfieldConvert {
+    fn process(&self, object: &mut ProcessObject) {
+        let surfaces = std::mem::take(&mut object.heightfields)
+            .into_iter()
+            .map(|field| {
+                let mut surface = field.to_surface();
+                for (mask, attribute) in &self.masks {
+                    let values = field.mask(mask).map_or_else(|| vec![0.0; field.len()], <[f32]>::to_vec);
+                    surface.mesh.insert_attribute(attribute.clone(), values);
+                }
+                surface
+            })
+            .collect();
+
+        if let Some(surface) = Surface::merge(surfaces) {
+            object.push_surface(surface);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
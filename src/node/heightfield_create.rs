@@ -0,0 +1,71 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec2};
+
+use crate::{geometry::Heightfield, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldCreateType;
+
+impl TypedNode for HeightfieldCreate {
+    type Type = HeightfieldCreateType;
+}
+
+/// Adds a flat heightfield over the XZ plane, centered on the origin.
+pub struct HeightfieldCreate {
+    pub size: Vec2,
+    pub columns: usize,
+    pub rows: usize,
+    pub height: f32,
+}
+
+impl HeightfieldCreate {
+    pub fn new(x_size: f32, z_size: f32) -> Self {
+        Self {
+            size: Vec2::new(x_size, z_size),
+            columns: 128,
+            rows: 128,
+            height: 0.0,
+        }
+    }
+
+    pub fn with_resolution(mut self, columns: usize, rows: usize) -> Self {
+        self.columns = columns;
+        self.rows = rows;
+        self
+    }
+
+    pub fn with_height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldCreateType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for HeightfieldCreate {
+    fn default() -> Self {
+        Self::new(10.0, 10.0)
+    }
+}
+
+impl CommonNode for HeightfieldCreate {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut field = Heightfield::new(self.size, self.columns, self.rows);
+        field.heights.iter_mut().for_each(|height| *height = self.height);
+        object.heightfields.push(field);
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,81 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{
+    geometry::{HydraulicErosion, ThermalErosion},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldErodeType;
+
+impl TypedNode for HeightfieldErode {
+    type Type = HeightfieldErodeType;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Erosion {
+    Thermal(ThermalErosion),
+    Hydraulic(HydraulicErosion),
+}
+
+/// Weathers every heightfield, with the material taken from each sample weighted by the `mask` layer when one is
+/// set. Hydraulic erosion also records where its droplets ran in the `flow` mask layer.
+pub struct HeightfieldErode {
+    pub erosion: Erosion,
+    pub mask: Option<String>,
+}
+
+impl HeightfieldErode {
+    pub fn new(erosion: Erosion) -> Self {
+        Self { erosion, mask: None }
+    }
+
+    pub fn thermal() -> Self {
+        Self::new(Erosion::Thermal(ThermalErosion::default()))
+    }
+
+    pub fn hydraulic() -> Self {
+        Self::new(Erosion::Hydraulic(HydraulicErosion::default()))
+    }
+
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = Some(mask.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldErodeType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for HeightfieldErode {
+    fn default() -> Self {
+        Self::hydraulic()
+    }
+}
+
+impl CommonNode for HeightfieldErode {
+    fn process(&self, object: &mut ProcessObject) {
+        for field in &mut object.heightfields {
+            let mask = self.mask.as_ref().and_then(|mask| field.masks.get(mask)).cloned();
+            match &self.erosion {
+                Erosion::Thermal(thermal) => thermal.apply(field, mask.as_deref()),
+                Erosion::Hydraulic(hydraulic) => hydraulic.apply(field, mask.as_deref()),
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,107 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldLayerType;
+
+impl TypedNode for HeightfieldLayer {
+    type Type = HeightfieldLayerType;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LayerMode {
+    Replace,
+    Add,
+    Subtract,
+    Multiply,
+    Max,
+    Min,
+}
+
+/// Combines every heightfield after the first into the first one, in order, and removes them. Layers are sampled
+/// where they overlap the first heightfield, so they can have any size and resolution; the blend is weighted by
+/// `opacity` and by the first heightfield's `mask` layer when one is set.
+pub struct HeightfieldLayer {
+    pub mode: LayerMode,
+    pub opacity: f32,
+    pub mask: Option<String>,
+}
+
+impl HeightfieldLayer {
+    pub fn new(mode: LayerMode) -> Self {
+        Self {
+            mode,
+            opacity: 1.0,
+            mask: None,
+        }
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = Some(mask.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldLayerType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for HeightfieldLayer {
+    fn default() -> Self {
+        Self::new(LayerMode::Add)
+    }
+}
+
+impl CommonNode for HeightfieldLayer {
+    fn process(&self, object: &mut ProcessObject) {
+        if object.heightfields.len() < 2 {
+            return;
+        }
+        let layers = object.heightfields.split_off(1);
+        let base = &mut object.heightfields[0];
+        let mask = self.mask.as_ref().and_then(|mask| base.masks.get(mask)).cloned();
+
+        for layer in &layers {
+            for row in 0..base.rows {
+                for column in 0..base.columns {
+                    let position = base.position(column, row);
+                    let value = match layer.sample(position.x, position.z) {
+                        Some(value) => value,
+                        None => continue,
+                    };
+                    let idx = base.index(column, row);
+                    let height = base.heights[idx];
+                    let blended = match self.mode {
+                        LayerMode::Replace => value,
+                        LayerMode::Add => height + value,
+                        LayerMode::Subtract => height - value,
+                        LayerMode::Multiply => height * value,
+                        LayerMode::Max => height.max(value),
+                        LayerMode::Min => height.min(value),
+                    };
+                    let weight = self.opacity * mask.as_ref().map_or(1.0, |mask| mask[idx]);
+                    base.heights[idx] = height + (blended - height) * weight;
+                }
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,157 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{
+    geometry::Heightfield, node::Finals, noise::NoiseSettings, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldMaskType;
+
+impl TypedNode for HeightfieldMask {
+    type Type = HeightfieldMaskType;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum MaskSource {
+    /// Samples with a height in the range, in world units.
+    Height { min: f32, max: f32 },
+    /// Samples with a slope in the range, in radians.
+    Slope { min: f32, max: f32 },
+    /// Noise over the XZ plane, mapped from `[-1, 1]` into `[0, 1]`.
+    Noise(NoiseSettings),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MaskCombine {
+    Replace,
+    Multiply,
+    Add,
+    Subtract,
+    Max,
+    Min,
+}
+
+/// Writes a mask layer of every heightfield from its heights, slopes or noise, combined with what the layer already
+/// holds. Range masks are 1 inside the range and fade out to 0 over `falloff` on either side.
+pub struct HeightfieldMask {
+    pub name: String,
+    pub source: MaskSource,
+    pub falloff: f32,
+    pub invert: bool,
+    pub combine: MaskCombine,
+}
+
+impl HeightfieldMask {
+    pub fn new(name: impl Into<String>, source: MaskSource) -> Self {
+        Self {
+            name: name.into(),
+            source,
+            falloff: 0.0,
+            invert: false,
+            combine: MaskCombine::Replace,
+        }
+    }
+
+    pub fn height(name: impl Into<String>, min: f32, max: f32) -> Self {
+        Self::new(name, MaskSource::Height { min, max })
+    }
+
+    pub fn slope(name: impl Into<String>, min: f32, max: f32) -> Self {
+        Self::new(name, MaskSource::Slope { min, max })
+    }
+
+    pub fn with_falloff(mut self, falloff: f32) -> Self {
+        self.falloff = falloff;
+        self
+    }
+
+    pub fn with_invert(mut self, invert: bool) -> Self {
+        self.invert = invert;
+        self
+    }
+
+    pub fn with_combine(mut self, combine: MaskCombine) -> Self {
+        self.combine = combine;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldMaskType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn values(&self, field: &Heightfield) -> Vec<f32> {
+        let in_range = |value: f32, min: f32, max: f32| {
+            let outside = (min - value).max(value - max);
+            if outside <= 0.0 {
+                1.0
+            } else if self.falloff <= 0.0 {
+                0.0
+            } else {
+                let t = (1.0 - outside / self.falloff).max(0.0);
+                t * t * (3.0 - 2.0 * t)
+            }
+        };
+        let sampler = match &self.source {
+            MaskSource::Noise(settings) => Some(settings.sampler()),
+            _ => None,
+        };
+
+        let mut values = Vec::with_capacity(field.len());
+        for row in 0..field.rows {
+            for column in 0..field.columns {
+                values.push(match (&self.source, &sampler) {
+                    (MaskSource::Height { min, max }, _) => in_range(field.height(column, row), *min, *max),
+                    (MaskSource::Slope { min, max }, _) => in_range(field.slope(column, row), *min, *max),
+                    (MaskSource::Noise(_), Some(sampler)) => {
+                        let mut position = field.position(column, row);
+                        position.y = 0.0;
+                        (sampler.sample(position) * 0.5 + 0.5).clamp(0.0, 1.0)
+                    },
+                    (MaskSource::Noise(_), None) => 0.0,
+                });
+            }
+        }
+        values
+    }
+}
+
+impl Default for HeightfieldMask {
+    fn default() -> Self {
+        Self::slope("mask", 30f32.to_radians(), 90f32.to_radians())
+    }
+}
+
+impl CommonNode for HeightfieldMask {
+    fn process(&self, object: &mut ProcessObject) {
+        for field in &mut object.heightfields {
+            let values = self.values(field);
+            let len = field.len();
+            let layer = field.masks.entry(self.name.clone()).or_insert_with(|| vec![0.0; len]);
+            for (existing, value) in layer.iter_mut().zip(values) {
+                let value = if self.invert { 1.0 - value } else { value };
+                *existing = match self.combine {
+                    MaskCombine::Replace => value,
+                    MaskCombine::Multiply => *existing * value,
+                    MaskCombine::Add => *existing + value,
+                    MaskCombine::Subtract => *existing - value,
+                    MaskCombine::Max => existing.max(value),
+                    MaskCombine::Min => existing.min(value),
+                }
+                .clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,109 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    node::Finals,
+    noise::{Fractal, NoiseBasis, NoiseSettings},
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct HeightfieldNoiseType;
+
+impl TypedNode for HeightfieldNoise {
+    type Type = HeightfieldNoiseType;
+}
+
+/// Raises every heightfield by noise sampled over the XZ plane, weighted by the `mask` layer when one is set.
+pub struct HeightfieldNoise {
+    pub settings: NoiseSettings,
+    pub mask: Option<String>,
+}
+
+impl HeightfieldNoise {
+    pub fn new(basis: NoiseBasis) -> Self {
+        Self {
+            settings: NoiseSettings {
+                basis,
+                ..Default::default()
+            },
+            mask: None,
+        }
+    }
+
+    pub fn with_fractal(mut self, fractal: Fractal) -> Self {
+        self.settings.fractal = fractal;
+        self
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.settings.frequency = frequency;
+        self
+    }
+
+    pub fn with_amplitude(mut self, amplitude: f32) -> Self {
+        self.settings.amplitude = amplitude;
+        self
+    }
+
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.settings.octaves = octaves;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.settings.offset = offset;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.settings.seed = seed;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = Some(mask.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((HeightfieldNoiseType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for HeightfieldNoise {
+    fn default() -> Self {
+        Self::new(NoiseBasis::Perlin)
+    }
+}
+
+impl CommonNode for HeightfieldNoise {
+    fn process(&self, object: &mut ProcessObject) {
+        let sampler = self.settings.sampler();
+
+        for field in &mut object.heightfields {
+            let mask = self.mask.as_ref().and_then(|mask| field.masks.get(mask)).cloned();
+            for row in 0..field.rows {
+                for column in 0..field.columns {
+                    let idx = field.index(column, row);
+                    let weight = mask.as_ref().map_or(1.0, |mask| mask[idx]);
+                    let mut position = field.position(column, row);
+                    position.y = 0.0;
+                    field.heights[idx] += weight * sampler.sample(position);
+                }
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,216 @@
+use std::{any::Any, collections::HashMap, f32::consts::PI};
+
+use bevy::{
+    prelude::{Commands, Component, Mesh, Quat, Vec3},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::{
+    geometry::{self, ATTRIBUTE_GENERATION, ATTRIBUTE_WIDTH},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct LSystemType;
+
+impl TypedNode for LSystem {
+    type Type = LSystemType;
+}
+
+/// Rewriting stops growing the string past this many symbols.
+const MAX_SYMBOLS: usize = 1_000_000;
+
+/// Rewrites the axiom with the rules for a number of generations, then draws the result with a 3D turtle that starts
+/// at the origin heading up +Y:
+///
+/// - `F`, `G` move forward by the step drawing a segment, `f` moves without drawing
+/// - `+`, `-` turn left and right by the angle, `&`, `^` pitch down and up, `\`, `/` roll left and right, `|` turns
+///   around
+/// - `[`, `]` push and pop the turtle state, starting a branch
+/// - `!` scales the width by `width_scale` and `"` the step by `step_scale`
+///
+/// Other symbols only take part in rewriting. The output is one line mesh with a separate curve per branch, ready to
+/// sweep, carrying the turtle width in `ATTRIBUTE_WIDTH` and the generation that created each segment in
+/// `ATTRIBUTE_GENERATION`.
+pub struct LSystem {
+    pub axiom: String,
+    pub rules: HashMap<char, String>,
+    pub generations: u32,
+    /// Turning angle in radians.
+    pub angle: f32,
+    pub step: f32,
+    pub step_scale: f32,
+    pub width: f32,
+    pub width_scale: f32,
+}
+
+impl LSystem {
+    pub fn new(axiom: impl Into<String>) -> Self {
+        Self {
+            axiom: axiom.into(),
+            rules: HashMap::new(),
+            generations: 3,
+            angle: 25f32.to_radians(),
+            step: 0.1,
+            step_scale: 0.9,
+            width: 0.02,
+            width_scale: 0.7,
+        }
+    }
+
+    pub fn with_rule(mut self, symbol: char, replacement: impl Into<String>) -> Self {
+        self.rules.insert(symbol, replacement.into());
+        self
+    }
+
+    pub fn with_generations(mut self, generations: u32) -> Self {
+        self.generations = generations;
+        self
+    }
+
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_step(mut self, step: f32, step_scale: f32) -> Self {
+        self.step = step;
+        self.step_scale = step_scale;
+        self
+    }
+
+    pub fn with_width(mut self, width: f32, width_scale: f32) -> Self {
+        self.width = width;
+        self.width_scale = width_scale;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((LSystemType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    /// Symbols after rewriting, each with the generation that produced it.
+    fn rewrite(&self) -> Vec<(char, u32)> {
+        let mut symbols = self.axiom.chars().map(|symbol| (symbol, 0)).collect::<Vec<_>>();
+        for generation in 1..=self.generations {
+            let mut next = Vec::with_capacity(symbols.len());
+            for (symbol, created) in symbols {
+                match self.rules.get(&symbol) {
+                    Some(replacement) => next.extend(replacement.chars().map(|symbol| (symbol, generation))),
+                    None => next.push((symbol, created)),
+                }
+            }
+            symbols = next;
+            if symbols.len() > MAX_SYMBOLS {
+                break;
+            }
+        }
+        symbols
+    }
+}
+
+impl Default for LSystem {
+    fn default() -> Self {
+        Self::new("X").with_rule('X', "F[+X][-X]&F[^X]FX").with_rule('F', "FF")
+    }
+}
+
+#[derive(Clone)]
+struct Turtle {
+    position: Vec3,
+    orientation: Quat,
+    step: f32,
+    width: f32,
+    /// Last vertex of the curve being drawn, if the turtle is drawing one.
+    vertex: Option<u32>,
+}
+
+impl CommonNode for LSystem {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut positions = Vec::new();
+        let mut widths = Vec::new();
+        let mut generations = Vec::new();
+        let mut indices = Vec::new();
+
+        let mut turtle = Turtle {
+            position: Vec3::ZERO,
+            orientation: Quat::IDENTITY,
+            step: self.step,
+            width: self.width,
+            vertex: None,
+        };
+        let mut stack = Vec::new();
+        let turn = |turtle: &mut Turtle, axis: Vec3, angle: f32| {
+            turtle.orientation = (turtle.orientation * Quat::from_axis_angle(axis, angle)).normalize();
+        };
+
+        for (symbol, generation) in self.rewrite() {
+            match symbol {
+                'F' | 'G' => {
+                    let mut add_vertex = |position: Vec3, width: f32| {
+                        positions.push(position.to_array());
+                        widths.push(width);
+                        generations.push(generation);
+                        positions.len() as u32 - 1
+                    };
+                    let start = match turtle.vertex {
+                        Some(vertex) => vertex,
+                        None => add_vertex(turtle.position, turtle.width),
+                    };
+                    turtle.position += turtle.orientation * Vec3::Y * turtle.step;
+                    let end = add_vertex(turtle.position, turtle.width);
+                    indices.extend([start, end]);
+                    turtle.vertex = Some(end);
+                },
+                'f' => {
+                    turtle.position += turtle.orientation * Vec3::Y * turtle.step;
+                    turtle.vertex = None;
+                },
+                '+' => turn(&mut turtle, Vec3::Z, self.angle),
+                '-' => turn(&mut turtle, Vec3::Z, -self.angle),
+                '&' => turn(&mut turtle, -Vec3::X, self.angle),
+                '^' => turn(&mut turtle, -Vec3::X, -self.angle),
+                '\\' => turn(&mut turtle, Vec3::Y, self.angle),
+                '/' => turn(&mut turtle, Vec3::Y, -self.angle),
+                '|' => turn(&mut turtle, Vec3::Z, PI),
+                '[' => {
+                    stack.push(turtle.clone());
+                    // branches start their own curve so that every branch can be swept on its own
+                    turtle.vertex = None;
+                },
+                ']' => {
+                    if let Some(saved) = stack.pop() {
+                        turtle = saved;
+                    }
+                },
+                '!' => turtle.width *= self.width_scale,
+                '"' => turtle.step *= self.step_scale,
+                _ => {},
+            }
+        }
+
+        if indices.is_empty() {
+            return;
+        }
+        let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; positions.len()]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(ATTRIBUTE_WIDTH, widths);
+        mesh.insert_attribute(ATTRIBUTE_GENERATION, generations);
+        geometry::set_indices(&mut mesh, indices);
+        geometry::insert_curve_uvs(&mut mesh);
+        object.meshes.push(mesh);
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
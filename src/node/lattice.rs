@@ -0,0 +1,134 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct LatticeType;
+
+impl TypedNode for Lattice {
+    type Type = LatticeType;
+}
+
+/// Free-form deformation: geometry inside the `min`..`max` box follows a grid of control points through Bernstein
+/// polynomial blending. Control points start on a regular grid, where they leave the geometry unchanged.
+pub struct Lattice {
+    pub min: Vec3,
+    pub max: Vec3,
+    pub divisions: [u32; 3],
+    pub points: Vec<Vec3>,
+    pub group: Option<String>,
+}
+
+impl Lattice {
+    pub fn new(min: Vec3, max: Vec3, divisions: [u32; 3]) -> Self {
+        let divisions = divisions.map(|count| count.max(2));
+        let mut points = Vec::with_capacity(divisions.iter().product::<u32>() as usize);
+        for k in 0..divisions[2] {
+            for j in 0..divisions[1] {
+                for i in 0..divisions[0] {
+                    let ratio = Vec3::new(
+                        i as f32 / (divisions[0] - 1) as f32,
+                        j as f32 / (divisions[1] - 1) as f32,
+                        k as f32 / (divisions[2] - 1) as f32,
+                    );
+                    points.push(min + (max - min) * ratio);
+                }
+            }
+        }
+
+        Self {
+            min,
+            max,
+            divisions,
+            points,
+            group: None,
+        }
+    }
+
+    pub fn with_point(mut self, i: u32, j: u32, k: u32, position: Vec3) -> Self {
+        *self.point_mut(i, j, k) = position;
+        self
+    }
+
+    pub fn point_mut(&mut self, i: u32, j: u32, k: u32) -> &mut Vec3 {
+        let idx = self.point_index(i, j, k);
+        &mut self.points[idx]
+    }
+
+    pub fn point_index(&self, i: u32, j: u32, k: u32) -> usize {
+        ((k * self.divisions[1] + j) * self.divisions[0] + i) as usize
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((LatticeType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn deform(&self, position: Vec3) -> Vec3 {
+        let size = self.max - self.min;
+        if size.min_element() <= 0.0 {
+            return position;
+        }
+
+        let ratio = (position - self.min) / size;
+        if ratio.min_element() < 0.0 || ratio.max_element() > 1.0 {
+            return position;
+        }
+
+        let weights_x = bernstein(self.divisions[0] - 1, ratio.x);
+        let weights_y = bernstein(self.divisions[1] - 1, ratio.y);
+        let weights_z = bernstein(self.divisions[2] - 1, ratio.z);
+
+        let mut result = Vec3::ZERO;
+        for (k, weight_z) in weights_z.iter().enumerate() {
+            for (j, weight_y) in weights_y.iter().enumerate() {
+                for (i, weight_x) in weights_x.iter().enumerate() {
+                    let point = self.points[self.point_index(i as u32, j as u32, k as u32)];
+                    result += point * (weight_x * weight_y * weight_z);
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Default for Lattice {
+    fn default() -> Self {
+        Self::new(Vec3::splat(-0.5), Vec3::splat(0.5), [2, 2, 2])
+    }
+}
+
+/// Bernstein basis polynomials of the given degree evaluated at `t`.
+fn bernstein(degree: u32, t: f32) -> Vec<f32> {
+    let mut binomial = 1.0;
+    (0..=degree)
+        .map(|idx| {
+            let weight = binomial * t.powi(idx as i32) * (1.0 - t).powi((degree - idx) as i32);
+            binomial = binomial * (degree - idx) as f32 / (idx + 1) as f32;
+            weight
+        })
+        .collect()
+}
+
+impl CommonNode for Lattice {
+    fn process(&self, object: &mut ProcessObject) {
+        geometry::deform_selected(object, self.group.as_deref(), |position| self.deform(position));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,67 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct LineType;
+
+impl TypedNode for Line {
+    type Type = LineType;
+}
+
+/// Adds a straight open curve of `points` evenly spaced points, `length` long from `start` along `direction`.
+pub struct Line {
+    pub start: Vec3,
+    pub direction: Vec3,
+    pub length: f32,
+    pub points: u32,
+}
+
+impl Line {
+    pub fn new(start: Vec3, direction: Vec3, length: f32) -> Self {
+        Self {
+            start,
+            direction,
+            length,
+            points: 2,
+        }
+    }
+
+    pub fn with_points(mut self, points: u32) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((LineType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Line {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, Vec3::Y, 1.0)
+    }
+}
+
+impl CommonNode for Line {
+    fn process(&self, object: &mut ProcessObject) {
+        let step = self.direction.normalize_or_zero() * self.length / (self.points.max(2) - 1) as f32;
+        let points = (0..self.points.max(2))
+            .map(|idx| self.start + step * idx as f32)
+            .collect::<Vec<_>>();
+        object.meshes.push(geometry::polyline(&points, false));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
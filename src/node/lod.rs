@@ -0,0 +1,117 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use bevy::prelude::{Assets, Camera, Camera3d, Component, GlobalTransform, Handle, Mesh, Query, ResMut, With};
+
+use crate::geometry;
+
+#[derive(Copy, Clone, Debug)]
+pub struct LodLevel {
+    /// Fraction of the cooked triangles kept at this level, in `[0, 1]`.
+    pub ratio: f32,
+    /// Camera distance from which this level is shown.
+    pub distance: f32,
+}
+
+impl LodLevel {
+    pub fn new(ratio: f32, distance: f32) -> Self {
+        Self { ratio, distance }
+    }
+}
+
+/// Levels of detail a `Final` generates from its cooked mesh.
+#[derive(Default, Component)]
+pub struct Lods(pub Vec<LodLevel>);
+
+impl Lods {
+    /// Level meshes decimated from the cooked mesh, along with the key they were made for. When `previous` was made
+    /// for the same mesh and levels its meshes are reused; otherwise they are removed from the assets.
+    pub fn cook(
+        &self,
+        mesh: Option<&Mesh>,
+        previous: Option<&LodMeshes>,
+        asset_meshes: &mut ResMut<Assets<Mesh>>,
+    ) -> (u64, Vec<(f32, Handle<Mesh>)>) {
+        let key = mesh.map_or(0, |mesh| self.key(mesh));
+        if let Some(previous) = previous {
+            if previous.key == key {
+                return (key, previous.levels.clone());
+            }
+            for (_, level) in &previous.levels {
+                asset_meshes.remove(level);
+            }
+        }
+
+        let mesh = match mesh {
+            Some(mesh) if !self.0.is_empty() => mesh,
+            _ => return (key, Vec::new()),
+        };
+
+        let triangles = geometry::triangles(mesh).len();
+        let locked = geometry::locked_vertices(mesh, true);
+        let mut levels = self
+            .0
+            .iter()
+            .map(|level| {
+                let target = (triangles as f32 * level.ratio.clamp(0.0, 1.0)).round() as usize;
+                let (reduced, _) = geometry::decimate(mesh, target, &locked);
+                (level.distance, asset_meshes.add(reduced))
+            })
+            .collect::<Vec<_>>();
+        levels.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        (key, levels)
+    }
+
+    /// Hash of the positions and triangles of a mesh together with the levels.
+    fn key(&self, mesh: &Mesh) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for level in &self.0 {
+            level.ratio.to_bits().hash(&mut hasher);
+            level.distance.to_bits().hash(&mut hasher);
+        }
+        for position in geometry::positions(mesh) {
+            position.to_array().map(f32::to_bits).hash(&mut hasher);
+        }
+        geometry::indices(mesh).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Cooked level meshes of a `Final`, sorted by increasing switch distance.
+#[derive(Component)]
+pub struct LodMeshes {
+    pub base: Handle<Mesh>,
+    pub levels: Vec<(f32, Handle<Mesh>)>,
+    /// Cooked mesh and levels the level meshes were made for, see [`Lods::cook`].
+    pub key: u64,
+}
+
+impl LodMeshes {
+    pub fn select(&self, distance: f32) -> &Handle<Mesh> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|(threshold, _)| distance >= *threshold)
+            .map(|(_, mesh)| mesh)
+            .unwrap_or(&self.base)
+    }
+}
+
+pub fn lod_update(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut finals: Query<(&LodMeshes, &GlobalTransform, &mut Handle<Mesh>)>,
+) {
+    let camera_position = match cameras.iter().find(|(camera, _)| camera.is_active) {
+        Some((_, transform)) => transform.translation(),
+        None => return,
+    };
+
+    for (lods, transform, mut mesh) in finals.iter_mut() {
+        let selected = lods.select(camera_position.distance(transform.translation()));
+        if *mesh != *selected {
+            *mesh = selected.clone();
+        }
+    }
+}
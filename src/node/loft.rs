@@ -0,0 +1,104 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{self, Surface},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct LoftType;
+
+impl TypedNode for Loft {
+    type Type = LoftType;
+}
+
+/// Skins a surface across the curves among the line meshes, in the order the inputs and the pieces within them
+/// come in. The line meshes are replaced by one surface with a quad face per curve segment between two curves.
+///
+/// Curves are resampled by arc length to `points` when it is set, or to the largest point count when the counts
+/// differ. Cross sections that are counter-clockwise when looking back from the next curve face outward.
+#[derive(Default)]
+pub struct Loft {
+    /// Connects the last curve back to the first.
+    pub closed: bool,
+    pub points: Option<u32>,
+}
+
+impl Loft {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    pub fn with_points(mut self, points: u32) -> Self {
+        self.points = Some(points);
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((LoftType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Loft {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut consumed = HashSet::new();
+        let mut curves = Vec::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            for curve in geometry::curves(mesh) {
+                consumed.insert(idx);
+                if curve.vertices.len() > 1 {
+                    curves.push((curve.points(mesh), curve.closed));
+                }
+            }
+        }
+        if curves.len() < 2 {
+            return;
+        }
+
+        let profile_closed = curves.iter().all(|(_, closed)| *closed);
+        let largest = curves.iter().map(|(points, _)| points.len()).max().unwrap_or(0);
+        let count = match self.points {
+            Some(points) => (points as usize).max(if profile_closed { 3 } else { 2 }),
+            None => largest,
+        };
+        let rings = curves
+            .into_iter()
+            .map(|(points, closed)| match points.len() == count {
+                true => points,
+                false => geometry::resample(&points, closed && profile_closed, count),
+            })
+            .collect::<Vec<_>>();
+
+        // V follows the distance between the centers of the curves
+        let centers = rings
+            .iter()
+            .map(|ring| ring.iter().fold(Vec3::ZERO, |sum, point| sum + *point) / ring.len() as f32)
+            .collect::<Vec<_>>();
+        let closed = self.closed && rings.len() > 2;
+        let lengths = geometry::arc_lengths(&centers, closed);
+        let total = lengths.last().copied().unwrap_or(0.0).max(f32::EPSILON);
+        let v = lengths.into_iter().map(|length| length / total).collect::<Vec<_>>();
+
+        object.remove_meshes(&consumed);
+        object.push_surface(Surface::rings(&rings, profile_closed, closed, &v));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,155 @@
+use std::any::Any;
+
+use bevy::{
+    prelude::{Commands, Component, Vec3},
+    render::mesh::MeshVertexAttribute,
+};
+
+use crate::{
+    geometry,
+    node::Finals,
+    noise::{Fractal, NoiseBasis, NoiseSettings},
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct NoiseType;
+
+impl TypedNode for Noise {
+    type Type = NoiseType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum NoiseDirection {
+    /// Points move along their normal by the noise value.
+    Normal,
+    /// Points move by an independent noise value on every axis.
+    Free,
+}
+
+pub struct Noise {
+    pub settings: NoiseSettings,
+    pub direction: NoiseDirection,
+    pub group: Option<String>,
+    pub mask_attribute: Option<MeshVertexAttribute>,
+}
+
+impl Noise {
+    pub fn new(basis: NoiseBasis) -> Self {
+        Self {
+            settings: NoiseSettings {
+                basis,
+                ..Default::default()
+            },
+            direction: NoiseDirection::Normal,
+            group: None,
+            mask_attribute: None,
+        }
+    }
+
+    pub fn with_fractal(mut self, fractal: Fractal) -> Self {
+        self.settings.fractal = fractal;
+        self
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.settings.frequency = frequency;
+        self
+    }
+
+    pub fn with_amplitude(mut self, amplitude: f32) -> Self {
+        self.settings.amplitude = amplitude;
+        self
+    }
+
+    pub fn with_octaves(mut self, octaves: u32) -> Self {
+        self.settings.octaves = octaves;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.settings.offset = offset;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.settings.seed = seed;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: NoiseDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn with_mask_attribute(mut self, attribute: MeshVertexAttribute) -> Self {
+        self.mask_attribute = Some(attribute);
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((NoiseType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        Self::new(NoiseBasis::Perlin)
+    }
+}
+
+impl CommonNode for Noise {
+    fn process(&self, object: &mut ProcessObject) {
+        let sampler = self.settings.sampler();
+
+        for idx in 0..object.meshes.len() {
+            let selected = self.group.as_ref().map(|group| object.selected_vertices(group, idx));
+            let mesh = &mut object.meshes[idx];
+
+            let mask = self
+                .mask_attribute
+                .as_ref()
+                .and_then(|attribute| geometry::scalars(mesh, attribute.id));
+            let normals = match self.direction {
+                NoiseDirection::Normal => match geometry::point_normals(mesh) {
+                    Some(normals) => Some(normals),
+                    None => continue,
+                },
+                NoiseDirection::Free => None,
+            };
+
+            let mut positions = geometry::positions(mesh);
+            for (vertex, position) in positions.iter_mut().enumerate() {
+                if matches!(&selected, Some(selected) if !selected.contains(&(vertex as u32))) {
+                    continue;
+                }
+                let weight = mask.as_ref().map_or(1.0, |mask| mask[vertex]);
+
+                *position += weight
+                    * match &normals {
+                        Some(normals) => normals[vertex] * sampler.sample(*position),
+                        None => sampler.sample_vec3(*position),
+                    };
+            }
+
+            geometry::set_positions(mesh, &positions);
+            geometry::compute_normals(mesh);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,96 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{geometry::Spline, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct NurbsType;
+
+impl TypedNode for Nurbs {
+    type Type = NurbsType;
+}
+
+/// Adds a NURBS curve from control points. Without knots, open curves are clamped to their end points and closed
+/// curves wrap around uniformly. The curve is a spline until it goes through `Resample`.
+pub struct Nurbs {
+    pub points: Vec<Vec3>,
+    /// Weight of every control point, one when missing.
+    pub weights: Vec<f32>,
+    pub degree: u32,
+    /// Knot vector of open curves, with as many entries as the points and the order together.
+    pub knots: Option<Vec<f32>>,
+    pub closed: bool,
+}
+
+impl Nurbs {
+    pub fn new(points: impl Into<Vec<Vec3>>) -> Self {
+        Self {
+            points: points.into(),
+            weights: Vec::new(),
+            degree: 3,
+            knots: None,
+            closed: false,
+        }
+    }
+
+    pub fn with_weights(mut self, weights: impl Into<Vec<f32>>) -> Self {
+        self.weights = weights.into();
+        self
+    }
+
+    pub fn with_degree(mut self, degree: u32) -> Self {
+        self.degree = degree;
+        self
+    }
+
+    pub fn with_knots(mut self, knots: impl Into<Vec<f32>>) -> Self {
+        self.knots = Some(knots.into());
+        self
+    }
+
+    pub fn with_closed(mut self, closed: bool) -> Self {
+        self.closed = closed;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((NurbsType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Nurbs {
+    fn default() -> Self {
+        Self::new([
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(-0.5, 1.0, 0.0),
+            Vec3::new(0.5, -1.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+        ])
+    }
+}
+
+impl CommonNode for Nurbs {
+    fn process(&self, object: &mut ProcessObject) {
+        if self.points.len() > 1 {
+            object.splines.push(Spline::nurbs(
+                self.points.clone(),
+                &self.weights,
+                self.degree as usize,
+                self.knots.clone(),
+                self.closed,
+            ));
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,117 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct PolyReduceType;
+
+impl TypedNode for PolyReduce {
+    type Type = PolyReduceType;
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum ReduceTarget {
+    /// Number of triangles to keep.
+    Count(u32),
+    /// Fraction of the input triangles to keep, in `[0, 1]`.
+    Percentage(f32),
+}
+
+impl ReduceTarget {
+    pub fn triangles(&self, current: usize) -> usize {
+        match *self {
+            Self::Count(count) => (count as usize).min(current),
+            Self::Percentage(percentage) => (current as f32 * percentage.clamp(0.0, 1.0)).round() as usize,
+        }
+    }
+}
+
+pub struct PolyReduce {
+    pub target: ReduceTarget,
+    pub preserve_boundaries: bool,
+    pub preserve_groups: bool,
+}
+
+impl PolyReduce {
+    pub fn new(target: ReduceTarget) -> Self {
+        Self {
+            target,
+            preserve_boundaries: true,
+            preserve_groups: true,
+        }
+    }
+
+    pub fn with_preserve_boundaries(mut self, preserve_boundaries: bool) -> Self {
+        self.preserve_boundaries = preserve_boundaries;
+        self
+    }
+
+    pub fn with_preserve_groups(mut self, preserve_groups: bool) -> Self {
+        self.preserve_groups = preserve_groups;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((PolyReduceType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for PolyReduce {
+    fn default() -> Self {
+        Self::new(ReduceTarget::Percentage(0.5))
+    }
+}
+
+impl CommonNode for PolyReduce {
+    fn process(&self, object: &mut ProcessObject) {
+        for idx in 0..object.meshes.len() {
+            let triangles = geometry::triangles(&object.meshes[idx]);
+            if triangles.is_empty() {
+                continue;
+            }
+
+            let mut locked = geometry::locked_vertices(&object.meshes[idx], self.preserve_boundaries);
+            if self.preserve_groups {
+                for group in object.selections.keys() {
+                    let selected = object.selected_vertices(group, idx);
+                    for tri in &triangles {
+                        let inside = tri.iter().filter(|vertex| selected.contains(vertex)).count();
+                        if inside != 0 && inside != 3 {
+                            for &vertex in tri {
+                                locked[vertex as usize] = true;
+                            }
+                        }
+                    }
+                }
+            }
+
+            let target = self.target.triangles(triangles.len());
+            let (reduced, remap) = geometry::decimate(&object.meshes[idx], target, &locked);
+            object.meshes[idx] = reduced;
+            object.polygons.remove(&idx);
+
+            for selection in object.selections.values_mut().flatten() {
+                if selection.mesh == idx {
+                    selection.indices = selection
+                        .indices
+                        .iter()
+                        .filter_map(|&vertex| remap[vertex as usize])
+                        .collect();
+                }
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,69 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Entity};
+
+use crate::{
+    node::{Finals, Parameters},
+    CommonNode, CookContext, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct PromotedType;
+
+impl TypedNode for Promoted {
+    type Type = PromotedType;
+}
+
+/// Makes the node to cook from the parameters in scope.
+pub type NodeBuilder = Box<dyn Fn(&Parameters) -> Box<dyn CommonNode> + Send + Sync>;
+
+/// Node inside a subnet definition whose settings come from the promoted parameters: `build` makes the node to
+/// cook from the parameters of the instance being cooked.
+pub struct Promoted {
+    pub build: NodeBuilder,
+}
+
+impl Promoted {
+    pub fn new<N: CommonNode>(build: impl Fn(&Parameters) -> N + Send + Sync + 'static) -> Self {
+        Self {
+            build: Box::new(move |parameters| Box::new(build(parameters))),
+        }
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((PromotedType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Promoted {
+    fn process(&self, object: &mut ProcessObject) {
+        (self.build)(&object.parameters).process(object);
+    }
+
+    /// Which inputs to cook is up to the built node, so they are cooked in [`CommonNode::cook`] once it exists.
+    fn active_inputs(&self, _count: usize) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn cook(&self, object: &mut ProcessObject, inputs: &[Entity], context: &mut CookContext) -> bool {
+        let node = (self.build)(&object.parameters);
+        for idx in node.active_inputs(inputs.len()) {
+            context.cook(inputs[idx], object);
+        }
+        if !node.cook(object, inputs, context) {
+            node.process(object);
+        }
+        true
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
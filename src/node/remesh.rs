@@ -0,0 +1,92 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct RemeshType;
+
+impl TypedNode for Remesh {
+    type Type = RemeshType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum RemeshMode {
+    /// Evenly sized, close to equilateral triangles.
+    Isotropic,
+    /// The isotropic result with neighbouring triangles joined into quads where they make a good one.
+    QuadDominant,
+}
+
+/// Rebuilds triangle meshes with edges close to `edge_length`. The new meshes only carry positions and normals, and
+/// selection groups on them are dropped.
+pub struct Remesh {
+    pub edge_length: f32,
+    pub iterations: u32,
+    pub mode: RemeshMode,
+}
+
+impl Remesh {
+    pub fn new(edge_length: f32) -> Self {
+        Self {
+            edge_length,
+            iterations: 5,
+            mode: RemeshMode::Isotropic,
+        }
+    }
+
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: RemeshMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((RemeshType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for Remesh {
+    fn default() -> Self {
+        Self::new(0.1)
+    }
+}
+
+impl CommonNode for Remesh {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut replaced = HashSet::new();
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            if geometry::triangles(mesh).is_empty() {
+                continue;
+            }
+
+            *mesh = geometry::remesh(mesh, self.edge_length, self.iterations);
+            match self.mode {
+                RemeshMode::Isotropic => object.polygons.remove(&idx),
+                RemeshMode::QuadDominant => object.polygons.insert(idx, geometry::pair_quads(mesh)),
+            };
+            replaced.insert(idx);
+        }
+
+        for selections in object.selections.values_mut() {
+            selections.retain(|selection| !replaced.contains(&selection.mesh));
+        }
+        object.selections.retain(|_, selections| !selections.is_empty());
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,112 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct ResampleType;
+
+impl TypedNode for Resample {
+    type Type = ResampleType;
+}
+
+/// Curves are cut into at most this many segments.
+const MAX_SEGMENTS: usize = 1_000_000;
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum ResampleMode {
+    /// Fixed number of segments per curve.
+    Segments(u32),
+    /// Segments as close to this length as fit evenly along each curve.
+    Length(f32),
+}
+
+/// Turns splines into line meshes, one per spline, and respaces the points of curves in existing line meshes, so
+/// that every curve ends up with segments of equal length, up to a million per curve. Selection groups on respaced
+/// meshes are dropped.
+pub struct Resample {
+    pub mode: ResampleMode,
+}
+
+impl Resample {
+    pub fn new(mode: ResampleMode) -> Self {
+        Self { mode }
+    }
+
+    pub fn segments(segments: u32) -> Self {
+        Self::new(ResampleMode::Segments(segments))
+    }
+
+    pub fn length(length: f32) -> Self {
+        Self::new(ResampleMode::Length(length))
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((ResampleType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn segment_count(&self, length: f32) -> usize {
+        let segments = match self.mode {
+            ResampleMode::Segments(segments) => segments.max(1) as usize,
+            ResampleMode::Length(segment_length) if segment_length > 0.0 => {
+                (length / segment_length).round().max(1.0) as usize
+            },
+            ResampleMode::Length(_) => 1,
+        };
+        segments.min(MAX_SEGMENTS)
+    }
+}
+
+impl Default for Resample {
+    fn default() -> Self {
+        Self::segments(10)
+    }
+}
+
+impl CommonNode for Resample {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut replaced = HashSet::new();
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let polylines = geometry::curves(mesh)
+                .into_iter()
+                .map(|curve| {
+                    let points = curve.points(mesh);
+                    let length = geometry::arc_lengths(&points, curve.closed)
+                        .last()
+                        .copied()
+                        .unwrap_or(0.0);
+                    let segments = self.segment_count(length);
+                    let count = if curve.closed { segments.max(3) } else { segments + 1 };
+                    geometry::polyline(&geometry::resample(&points, curve.closed, count), curve.closed)
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(resampled) = geometry::merge(&polylines) {
+                *mesh = resampled;
+                replaced.insert(idx);
+            }
+        }
+
+        for spline in std::mem::take(&mut object.splines) {
+            let points = spline.resample(self.segment_count(spline.length()));
+            object.meshes.push(geometry::polyline(&points, spline.closed));
+        }
+
+        for selections in object.selections.values_mut() {
+            selections.retain(|selection| !replaced.contains(&selection.mesh));
+        }
+        object.selections.retain(|_, selections| !selections.is_empty());
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,149 @@
+use std::{any::Any, collections::HashSet, f32::consts::TAU};
+
+use bevy::prelude::{Commands, Component, Quat, Vec3};
+
+use crate::{
+    geometry::{self, Surface},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct RevolveType;
+
+impl TypedNode for Revolve {
+    type Type = RevolveType;
+}
+
+/// Spins every curve among the line meshes around an axis through `origin`, by `angle` radians in `divisions`
+/// steps. The line meshes are replaced by one surface with a quad face per profile segment and step.
+///
+/// A profile running along the axis direction faces away from the axis. On full revolutions the last step ends on
+/// the exact positions of the first profile, so the seam is one set of points split only for UVs; partial
+/// revolutions of closed profiles are closed with caps across the start and end profiles.
+pub struct Revolve {
+    pub origin: Vec3,
+    pub axis: Vec3,
+    pub angle: f32,
+    pub divisions: u32,
+    pub caps: bool,
+}
+
+impl Revolve {
+    pub fn new(origin: Vec3, axis: Vec3) -> Self {
+        Self {
+            origin,
+            axis,
+            angle: TAU,
+            divisions: 16,
+            caps: true,
+        }
+    }
+
+    pub fn with_angle(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn with_divisions(mut self, divisions: u32) -> Self {
+        self.divisions = divisions;
+        self
+    }
+
+    pub fn with_caps(mut self, caps: bool) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((RevolveType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn is_full(&self) -> bool {
+        self.angle.abs() >= TAU - 1e-4
+    }
+
+    fn revolve(&self, profile: &[Vec3], profile_closed: bool) -> Vec<Surface> {
+        let axis = self.axis.normalize();
+        // points on the axis stay where they are so that all their copies coincide
+        let on_axis = profile
+            .iter()
+            .map(|point| point.distance(self.origin + axis * axis.dot(*point - self.origin)) < 1e-6)
+            .collect::<Vec<_>>();
+
+        let full = self.is_full();
+        let divisions = self.divisions.max(1);
+        let ring_count = if full { divisions } else { divisions + 1 };
+        let rings = (0..ring_count)
+            .map(|step| {
+                let rotation = Quat::from_axis_angle(axis, self.angle * step as f32 / divisions as f32);
+                profile
+                    .iter()
+                    .zip(&on_axis)
+                    .map(|(point, on_axis)| match on_axis {
+                        true => *point,
+                        false => self.origin + rotation * (*point - self.origin),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let v = (0..=divisions)
+            .map(|step| step as f32 / divisions as f32)
+            .collect::<Vec<_>>();
+
+        let mut sides = Surface::rings(&rings, profile_closed, full, &v);
+        sides.flip();
+        let mut surfaces = vec![sides];
+
+        if self.caps && profile_closed && !full {
+            let center = profile.iter().fold(Vec3::ZERO, |sum, point| sum + *point) / profile.len() as f32;
+            let direction = |rotation: Quat| rotation * axis.cross(center - self.origin) * self.angle.signum();
+            if let (Some(first), Some(last)) = (rings.first(), rings.last()) {
+                surfaces.push(Surface::cap(first, -direction(Quat::IDENTITY)));
+                surfaces.push(Surface::cap(last, direction(Quat::from_axis_angle(axis, self.angle))));
+            }
+        }
+        surfaces
+    }
+}
+
+impl Default for Revolve {
+    fn default() -> Self {
+        Self::new(Vec3::ZERO, Vec3::Y)
+    }
+}
+
+impl CommonNode for Revolve {
+    fn process(&self, object: &mut ProcessObject) {
+        if self.axis == Vec3::ZERO || self.angle == 0.0 {
+            return;
+        }
+
+        let mut consumed = HashSet::new();
+        let mut surfaces = Vec::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            for curve in geometry::curves(mesh) {
+                consumed.insert(idx);
+                if curve.vertices.len() > 1 {
+                    surfaces.extend(self.revolve(&curve.points(mesh), curve.closed));
+                }
+            }
+        }
+
+        object.remove_meshes(&consumed);
+        if let Some(surface) = Surface::merge(surfaces) {
+            object.push_surface(surface);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
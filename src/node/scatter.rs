@@ -0,0 +1,260 @@
+use std::{
+    any::Any,
+    collections::{BinaryHeap, HashSet},
+};
+
+use bevy::{
+    prelude::{Commands, Component, Mesh, Vec3},
+    render::mesh::{MeshVertexAttribute, PrimitiveTopology},
+};
+
+use crate::{
+    geometry::{self, Random, SpatialHash},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct ScatterType;
+
+impl TypedNode for Scatter {
+    type Type = ScatterType;
+}
+
+/// How many candidates per output point are generated before Poisson-disk elimination.
+const POISSON_OVERSAMPLING: usize = 5;
+
+pub struct Scatter {
+    pub count: u32,
+    pub seed: u64,
+    pub density_attribute: Option<MeshVertexAttribute>,
+    pub group: Option<String>,
+    pub poisson_disk: bool,
+}
+
+impl Scatter {
+    pub fn new(count: u32) -> Self {
+        Self {
+            count,
+            seed: 0,
+            density_attribute: None,
+            group: None,
+            poisson_disk: false,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_density_attribute(mut self, attribute: MeshVertexAttribute) -> Self {
+        self.density_attribute = Some(attribute);
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn with_poisson_disk(mut self, poisson_disk: bool) -> Self {
+        self.poisson_disk = poisson_disk;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((ScatterType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn scatter(&self, mesh: &Mesh, selected: Option<&HashSet<u32>>, random: &mut Random) -> Mesh {
+        let positions = geometry::positions(mesh);
+        let normals = geometry::normals(mesh);
+        let density = self
+            .density_attribute
+            .as_ref()
+            .and_then(|attribute| geometry::scalars(mesh, attribute.id));
+
+        let triangles = geometry::triangles(mesh)
+            .into_iter()
+            .filter(|tri| match selected {
+                Some(selected) => tri.iter().all(|idx| selected.contains(idx)),
+                None => true,
+            })
+            .collect::<Vec<_>>();
+
+        let mut area = 0.0;
+        let mut cumulative = Vec::with_capacity(triangles.len());
+        let mut total_weight = 0.0;
+        for [a, b, c] in &triangles {
+            let tri_area =
+                geometry::triangle_area(positions[*a as usize], positions[*b as usize], positions[*c as usize]);
+            let tri_density = density.as_ref().map_or(1.0, |density| {
+                ((density[*a as usize] + density[*b as usize] + density[*c as usize]) / 3.0).max(0.0)
+            });
+            area += tri_area;
+            total_weight += tri_area * tri_density;
+            cumulative.push(total_weight);
+        }
+
+        let mut points = Vec::new();
+        let mut point_normals = Vec::new();
+        if total_weight > 0.0 {
+            let candidates = if self.poisson_disk {
+                self.count as usize * POISSON_OVERSAMPLING
+            } else {
+                self.count as usize
+            };
+
+            for _ in 0..candidates {
+                let target = random.next_f32() * total_weight;
+                let tri_idx = cumulative
+                    .partition_point(|&weight| weight <= target)
+                    .min(triangles.len() - 1);
+                let [a, b, c] = triangles[tri_idx].map(|idx| idx as usize);
+
+                let (mut u, mut v) = (random.next_f32(), random.next_f32());
+                if u + v > 1.0 {
+                    u = 1.0 - u;
+                    v = 1.0 - v;
+                }
+                let w = 1.0 - u - v;
+
+                points.push(positions[a] * w + positions[b] * u + positions[c] * v);
+                point_normals.push(match &normals {
+                    Some(normals) => (normals[a] * w + normals[b] * u + normals[c] * v).normalize_or_zero(),
+                    None => geometry::triangle_normal(positions[a], positions[b], positions[c]),
+                });
+            }
+
+            if self.poisson_disk {
+                let keep = eliminate_samples(&points, self.count as usize, area);
+                points = keep.iter().map(|&idx| points[idx]).collect();
+                point_normals = keep.iter().map(|&idx| point_normals[idx]).collect();
+            }
+        }
+
+        let mut result = Mesh::new(PrimitiveTopology::PointList);
+        geometry::set_positions(&mut result, &points);
+        result.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            point_normals.iter().map(|normal| normal.to_array()).collect::<Vec<_>>(),
+        );
+        result
+    }
+}
+
+impl Default for Scatter {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+/// Weighted sample elimination (Yuksel 2015): repeatedly drops the sample most crowded by its neighbours until
+/// `count` remain, which yields Poisson-disk distributed points without picking a radius up front.
+fn eliminate_samples(points: &[Vec3], count: usize, area: f32) -> Vec<usize> {
+    if points.len() <= count || count == 0 {
+        return (0..points.len().min(count)).collect();
+    }
+
+    let max_radius = (area / (2.0 * 3f32.sqrt() * count as f32)).sqrt();
+    let max_distance = max_radius * 2.0;
+    let weight = |a: Vec3, b: Vec3| {
+        let distance = a.distance(b);
+        if distance < max_distance {
+            (1.0 - distance / max_distance).powi(8)
+        } else {
+            0.0
+        }
+    };
+
+    let mut hash = SpatialHash::new(max_distance);
+    for (idx, point) in points.iter().enumerate() {
+        hash.insert(idx as u32, *point);
+    }
+
+    let mut weights = points
+        .iter()
+        .enumerate()
+        .map(|(idx, point)| {
+            hash.neighbors(*point)
+                .filter(|&other| other as usize != idx)
+                .map(|other| weight(*point, points[other as usize]))
+                .sum::<f32>()
+        })
+        .collect::<Vec<_>>();
+
+    // weights are never negative so their bit patterns order the same way as the values
+    let mut heap = weights
+        .iter()
+        .enumerate()
+        .map(|(idx, weight)| (weight.to_bits(), idx))
+        .collect::<BinaryHeap<_>>();
+    let mut alive = vec![true; points.len()];
+    let mut remaining = points.len();
+
+    while remaining > count {
+        let (bits, idx) = match heap.pop() {
+            Some(entry) => entry,
+            None => break,
+        };
+        if !alive[idx] || weights[idx].to_bits() != bits {
+            continue;
+        }
+
+        alive[idx] = false;
+        remaining -= 1;
+        hash.remove(idx as u32, points[idx]);
+
+        for other in hash
+            .neighbors(points[idx])
+            .map(|other| other as usize)
+            .collect::<Vec<_>>()
+        {
+            let shared = weight(points[idx], points[other]);
+            if shared > 0.0 {
+                weights[other] = (weights[other] - shared).max(0.0);
+                heap.push((weights[other].to_bits(), other));
+            }
+        }
+    }
+
+    (0..points.len()).filter(|&idx| alive[idx]).collect()
+}
+
+impl CommonNode for Scatter {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut random = Random::new(self.seed);
+        let mut replaced = HashSet::new();
+
+        for idx in 0..object.meshes.len() {
+            if !matches!(
+                object.meshes[idx].primitive_topology(),
+                PrimitiveTopology::TriangleList | PrimitiveTopology::TriangleStrip
+            ) {
+                continue;
+            }
+
+            let selected = self.group.as_ref().map(|group| object.selected_vertices(group, idx));
+            object.meshes[idx] = self.scatter(&object.meshes[idx], selected.as_ref(), &mut random);
+            replaced.insert(idx);
+        }
+
+        for selections in object.selections.values_mut() {
+            selections.retain(|selection| !replaced.contains(&selection.mesh));
+        }
+        object.selections.retain(|_, selections| !selections.is_empty());
+        object.polygons.retain(|mesh, _| !replaced.contains(mesh));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -15,6 +15,7 @@ impl TypedNode for SelectionGroup {
     type Type = SelectionGroupType;
 }
 
+#[derive(Clone)]
 pub struct Selection {
     pub mesh: usize,
     pub indices: Vec<u32>,
@@ -0,0 +1,145 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{self, Topology},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct SmoothType;
+
+impl TypedNode for Smooth {
+    type Type = SmoothType;
+}
+
+/// Pass-band frequency of Taubin smoothing, which determines the inflating step from the shrinking one.
+const TAUBIN_PASS_BAND: f32 = 0.1;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum SmoothMethod {
+    /// Moves every point towards the average of its neighbours; shrinks the mesh over many iterations.
+    Laplacian,
+    /// Alternates a shrinking and an inflating Laplacian step so that the volume is roughly preserved.
+    Taubin,
+}
+
+pub struct Smooth {
+    pub method: SmoothMethod,
+    pub iterations: u32,
+    pub strength: f32,
+    pub pin_boundary: bool,
+    pub group: Option<String>,
+}
+
+impl Smooth {
+    pub fn new(method: SmoothMethod, iterations: u32) -> Self {
+        Self {
+            method,
+            iterations,
+            strength: 0.5,
+            pin_boundary: true,
+            group: None,
+        }
+    }
+
+    pub fn with_strength(mut self, strength: f32) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    pub fn with_pin_boundary(mut self, pin_boundary: bool) -> Self {
+        self.pin_boundary = pin_boundary;
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SmoothType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn steps(&self) -> Vec<f32> {
+        match self.method {
+            SmoothMethod::Laplacian => vec![self.strength],
+            SmoothMethod::Taubin => vec![self.strength, 1.0 / (TAUBIN_PASS_BAND - 1.0 / self.strength)],
+        }
+    }
+}
+
+impl Default for Smooth {
+    fn default() -> Self {
+        Self::new(SmoothMethod::Taubin, 10)
+    }
+}
+
+impl CommonNode for Smooth {
+    fn process(&self, object: &mut ProcessObject) {
+        let steps = self.steps();
+
+        for idx in 0..object.meshes.len() {
+            let selected = self.group.as_ref().map(|group| object.selected_vertices(group, idx));
+            let mesh = &mut object.meshes[idx];
+
+            let topology = Topology::new(mesh);
+            if topology.triangles.is_empty() {
+                continue;
+            }
+
+            let neighbors = topology.neighbors();
+            let mut movable = match &selected {
+                Some(selected) => {
+                    let mut movable = vec![false; topology.points.len()];
+                    for &vertex in selected {
+                        movable[topology.vertex_points[vertex as usize] as usize] = true;
+                    }
+                    movable
+                },
+                None => vec![true; topology.points.len()],
+            };
+            if self.pin_boundary {
+                for (movable, boundary) in movable.iter_mut().zip(topology.boundary_points()) {
+                    *movable &= !boundary;
+                }
+            }
+
+            let mut points = topology.points.clone();
+            for _ in 0..self.iterations {
+                for &step in &steps {
+                    points = points
+                        .iter()
+                        .enumerate()
+                        .map(|(point, position)| {
+                            let around = &neighbors[point];
+                            if !movable[point] || around.is_empty() {
+                                return *position;
+                            }
+                            let average =
+                                around.iter().map(|&other| points[other as usize]).sum::<Vec3>() / around.len() as f32;
+                            *position + (average - *position) * step
+                        })
+                        .collect();
+                }
+            }
+
+            topology.apply(mesh, &points);
+            geometry::compute_normals(mesh);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
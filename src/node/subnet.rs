@@ -0,0 +1,288 @@
+use std::{any::Any, collections::HashMap};
+
+use bevy::prelude::{Commands, Component, Entity, Vec3};
+
+use crate::{node::Finals, CommonNode, CookContext, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct SubnetDefinitionType;
+
+impl TypedNode for SubnetDefinition {
+    type Type = SubnetDefinitionType;
+}
+
+#[derive(Copy, Clone, Component)]
+pub struct SubnetType;
+
+impl TypedNode for Subnet {
+    type Type = SubnetType;
+}
+
+#[derive(Copy, Clone, Component)]
+pub struct SubnetInputType;
+
+impl TypedNode for SubnetInput {
+    type Type = SubnetInputType;
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Parameter {
+    Float(f32),
+    Int(i32),
+    Bool(bool),
+    Vec3(Vec3),
+    Text(String),
+}
+
+impl From<f32> for Parameter {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<i32> for Parameter {
+    fn from(value: i32) -> Self {
+        Self::Int(value)
+    }
+}
+
+impl From<bool> for Parameter {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<Vec3> for Parameter {
+    fn from(value: Vec3) -> Self {
+        Self::Vec3(value)
+    }
+}
+
+impl From<&str> for Parameter {
+    fn from(value: &str) -> Self {
+        Self::Text(value.into())
+    }
+}
+
+impl From<String> for Parameter {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+/// Named parameter values in scope while a subnet instance cooks.
+#[derive(Default, Clone, Debug)]
+pub struct Parameters(pub HashMap<String, Parameter>);
+
+impl Parameters {
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<Parameter>) {
+        self.0.insert(name.into(), value.into());
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Parameter> {
+        self.0.get(name)
+    }
+
+    /// Float value of the parameter, converting from an integer one.
+    pub fn float(&self, name: &str) -> Option<f32> {
+        match self.get(name)? {
+            Parameter::Float(value) => Some(*value),
+            Parameter::Int(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn int(&self, name: &str) -> Option<i32> {
+        match self.get(name)? {
+            Parameter::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            Parameter::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn vec3(&self, name: &str) -> Option<Vec3> {
+        match self.get(name)? {
+            Parameter::Vec3(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            Parameter::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Reusable subgraph. Its only input child is the output node of the subgraph, which reads the inputs of an
+/// instance through [`SubnetInput`] nodes and its parameters through `Promoted` nodes.
+///
+/// The subgraph cooks once per [`Subnet`] instance, so editing any of its nodes or the parameter defaults here
+/// re-cooks every final that uses an instance.
+#[derive(Default)]
+pub struct SubnetDefinition {
+    pub inputs: Vec<String>,
+    pub parameters: Parameters,
+}
+
+impl SubnetDefinition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares the next input; [`SubnetInput`] refers to inputs by their position in declaration order.
+    pub fn with_input(mut self, name: impl Into<String>) -> Self {
+        self.inputs.push(name.into());
+        self
+    }
+
+    /// Promotes a parameter with its default value.
+    pub fn with_parameter(mut self, name: impl Into<String>, default: impl Into<Parameter>) -> Self {
+        self.parameters.set(name, default);
+        self
+    }
+
+    pub fn input_index(&self, name: &str) -> Option<u32> {
+        self.inputs.iter().position(|input| input == name).map(|idx| idx as u32)
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SubnetDefinitionType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for SubnetDefinition {
+    fn process(&self, _object: &mut ProcessObject) {}
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Instance of a [`SubnetDefinition`]. Its input children are the declared inputs of the definition in order, and
+/// its parameters override the promoted defaults.
+pub struct Subnet {
+    pub definition: Entity,
+    pub parameters: Parameters,
+}
+
+impl Subnet {
+    pub fn new(definition: Entity) -> Self {
+        Self {
+            definition,
+            parameters: Parameters::default(),
+        }
+    }
+
+    pub fn with_parameter(mut self, name: impl Into<String>, value: impl Into<Parameter>) -> Self {
+        self.parameters.set(name, value);
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SubnetType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Subnet {
+    /// The definition is cooked in [`CommonNode::cook`], which has access to the graph.
+    fn process(&self, _object: &mut ProcessObject) {}
+
+    /// Inputs are cooked where the definition reads them through [`SubnetInput`] nodes.
+    fn active_inputs(&self, _count: usize) -> Vec<usize> {
+        Vec::new()
+    }
+
+    fn cook(&self, object: &mut ProcessObject, inputs: &[Entity], context: &mut CookContext) -> bool {
+        let mut parameters = context
+            .node(self.definition)
+            .and_then(|node| node.as_any_ref().downcast_ref::<SubnetDefinition>())
+            .map(|definition| definition.parameters.clone())
+            .unwrap_or_default();
+        parameters.0.extend(self.parameters.0.clone());
+
+        let outer = std::mem::replace(&mut object.parameters, parameters);
+        context.scopes.push(SubnetScope {
+            inputs: inputs.to_vec(),
+            parameters: outer,
+        });
+        context.cook(self.definition, object);
+        object.parameters = context.scopes.pop().unwrap().parameters;
+        true
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Instance whose definition is being cooked: its inputs and the parameters in scope around it.
+pub struct SubnetScope {
+    pub inputs: Vec<Entity>,
+    pub parameters: Parameters,
+}
+
+/// Placeholder inside a subnet definition for the input at `index` of the instance being cooked.
+#[derive(Default)]
+pub struct SubnetInput {
+    pub index: u32,
+}
+
+impl SubnetInput {
+    pub fn new(index: u32) -> Self {
+        Self { index }
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SubnetInputType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for SubnetInput {
+    fn process(&self, _object: &mut ProcessObject) {}
+
+    fn cook(&self, object: &mut ProcessObject, _inputs: &[Entity], context: &mut CookContext) -> bool {
+        // the input belongs to the graph around the instance, so it cooks in the scope outside of it
+        if let Some(scope) = context.scopes.pop() {
+            let inner = std::mem::replace(&mut object.parameters, scope.parameters.clone());
+            if let Some(&input) = scope.inputs.get(self.index as usize) {
+                context.cook(input, object);
+            }
+            object.parameters = inner;
+            context.scopes.push(scope);
+        }
+        true
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
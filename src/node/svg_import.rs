@@ -0,0 +1,174 @@
+use std::{any::Any, path::PathBuf};
+
+use bevy::prelude::{Commands, Component, Mesh, Vec2, Vec3};
+
+use crate::{
+    geometry::{self, Spline, Surface},
+    node::{Finals, Selection},
+    svg, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct SvgImportType;
+
+impl TypedNode for SvgImport {
+    type Type = SvgImportType;
+}
+
+/// Reads the paths and basic shapes of a local SVG file into the XY plane, with the document's y axis flipped so
+/// that the drawing stands upright, and `scale` world units per document unit.
+///
+/// Outlines come in as one line mesh, except for subpaths with Bezier curves, which come in as splines so that
+/// `Resample` decides how finely they are cut. With `fill` set the filled elements become polygons facing +Z instead,
+/// cut by their fill rule, and elements without a fill are left out. Elements with an `id` become a selection group
+/// of that name, covering the parts of them that are meshes.
+pub struct SvgImport {
+    pub path: PathBuf,
+    pub scale: f32,
+    /// Segments that every quarter turn of an arc is cut into, and with `fill` every curve.
+    pub curve_segments: u32,
+    pub fill: bool,
+}
+
+impl SvgImport {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            scale: 0.01,
+            curve_segments: 8,
+            fill: false,
+        }
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_curve_segments(mut self, curve_segments: u32) -> Self {
+        self.curve_segments = curve_segments;
+        self
+    }
+
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SvgImportType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn place(&self, points: &[Vec2]) -> Vec<Vec3> {
+        points
+            .iter()
+            .map(|point| Vec3::new(point.x, -point.y, 0.0) * self.scale)
+            .collect()
+    }
+
+    fn fill(&self, shape: svg::SvgShape) -> Option<Surface> {
+        let loops = shape.subpaths.into_iter().map(|subpath| subpath.points).collect();
+        let surfaces = geometry::nest_loops(loops, shape.fill_rule)
+            .into_iter()
+            .map(|(outer, holes)| {
+                let holes = holes.iter().map(|hole| self.place(hole)).collect::<Vec<_>>();
+                Surface::cap_with_holes(&self.place(&outer), &holes, Vec3::Z)
+            })
+            .collect();
+        Surface::merge(surfaces)
+    }
+
+    fn spline(&self, subpath: &svg::Subpath) -> Spline {
+        let mut spline = Spline::bezier(self.place(&subpath.curve), 3);
+        // a closed chain ends on its start point, which closed splines leave out when sampled
+        spline.closed = subpath.closed;
+        spline
+    }
+
+    fn outline(&self, shape: svg::SvgShape) -> Option<Mesh> {
+        let polylines = shape
+            .subpaths
+            .iter()
+            .map(|subpath| geometry::polyline(&self.place(&subpath.points), subpath.closed))
+            .collect::<Vec<_>>();
+        geometry::merge(&polylines)
+    }
+}
+
+impl Default for SvgImport {
+    fn default() -> Self {
+        Self::new("")
+    }
+}
+
+impl CommonNode for SvgImport {
+    fn process(&self, object: &mut ProcessObject) {
+        let source = match std::fs::read_to_string(&self.path) {
+            Ok(source) => source,
+            Err(err) => {
+                eprintln!("SVG could not be read from {:?}: {:?}", self.path, err);
+                return;
+            },
+        };
+
+        let mut surfaces = Vec::new();
+        let mut outlines = Vec::new();
+        for mut shape in svg::parse(&source, self.curve_segments) {
+            let id = shape.id.clone();
+            if !self.fill {
+                for subpath in shape.subpaths.iter().filter(|subpath| !subpath.curve.is_empty()) {
+                    object.splines.push(self.spline(subpath));
+                }
+                shape.subpaths.retain(|subpath| subpath.curve.is_empty());
+                outlines.extend(self.outline(shape).map(|mesh| (id, mesh)));
+            } else if shape.filled {
+                surfaces.extend(self.fill(shape).map(|surface| (id, surface)));
+            }
+        }
+
+        let mesh = object.meshes.len();
+        let counts = outlines
+            .iter()
+            .map(|(id, mesh)| (id.clone(), mesh.count_vertices()))
+            .chain(
+                surfaces
+                    .iter()
+                    .map(|(id, surface)| (id.clone(), surface.mesh.count_vertices())),
+            )
+            .collect::<Vec<_>>();
+        if self.fill {
+            match Surface::merge(surfaces.into_iter().map(|(_, surface)| surface).collect()) {
+                Some(surface) => object.push_surface(surface),
+                None => return,
+            }
+        } else {
+            match geometry::merge(&outlines.into_iter().map(|(_, mesh)| mesh).collect::<Vec<_>>()) {
+                Some(outline) => object.meshes.push(outline),
+                None => return,
+            }
+        }
+
+        let mut offset = 0;
+        for (id, count) in counts {
+            let count = count as u32;
+            if let Some(id) = id {
+                object.selections.entry(id).or_default().push(Selection {
+                    mesh,
+                    indices: (offset..offset + count).collect(),
+                });
+            }
+            offset += count;
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,191 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::{
+    prelude::{Commands, Component, Quat, Vec3},
+    render::mesh::VertexAttributeValues,
+};
+
+use crate::{
+    geometry::{self, Ramp, Surface, ATTRIBUTE_WIDTH},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct SweepType;
+
+impl TypedNode for Sweep {
+    type Type = SweepType;
+}
+
+/// Sweeps a profile curve along path curves. The first curve among the line meshes is the profile and every other
+/// curve is a path; the line meshes are replaced by one surface with a quad face per profile segment and path step.
+///
+/// The profile is placed in rotation minimizing frames along the path, with its x axis on the frame normal, its y
+/// axis on the binormal and its z axis along the path. `scale` and `twist` (in radians) are sampled over the
+/// normalized path length, and paths with an `ATTRIBUTE_WIDTH` scale the profile by it as well. V runs along the
+/// path in path units while U runs around the profile.
+pub struct Sweep {
+    pub scale: Ramp,
+    pub twist: Ramp,
+    /// Closes the ends of open paths when the profile is closed.
+    pub caps: bool,
+}
+
+impl Sweep {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_scale(mut self, scale: Ramp) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_twist(mut self, twist: Ramp) -> Self {
+        self.twist = twist;
+        self
+    }
+
+    pub fn with_caps(mut self, caps: bool) -> Self {
+        self.caps = caps;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SweepType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn sweep(&self, profile: &Path, path: &Path) -> Vec<Surface> {
+        let frames = geometry::rotation_minimizing_frames(&path.points, path.closed);
+        let lengths = geometry::arc_lengths(&path.points, path.closed);
+        let total = lengths.last().copied().unwrap_or(0.0).max(f32::EPSILON);
+
+        let rings = path
+            .points
+            .iter()
+            .zip(&frames)
+            .zip(&lengths)
+            .zip(&path.widths)
+            .map(|(((point, (tangent, normal)), length), width)| {
+                let t = length / total;
+                let scale = self.scale.sample(t) * width;
+                let rotation = Quat::from_axis_angle(*tangent, self.twist.sample(t));
+                let normal = rotation * *normal;
+                let binormal = tangent.cross(normal);
+                profile
+                    .points
+                    .iter()
+                    .map(|local| *point + (normal * local.x + binormal * local.y) * scale + *tangent * local.z)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut surfaces = vec![Surface::rings(&rings, profile.closed, path.closed, &lengths)];
+        if self.caps && profile.closed && !path.closed {
+            if let (Some(first), Some(last)) = (rings.first(), rings.last()) {
+                surfaces.push(Surface::cap(first, -frames[0].0));
+                surfaces.push(Surface::cap(last, frames[frames.len() - 1].0));
+            }
+        }
+        surfaces
+    }
+}
+
+impl Default for Sweep {
+    fn default() -> Self {
+        Self {
+            scale: Ramp::constant(1.0),
+            twist: Ramp::constant(0.0),
+            caps: true,
+        }
+    }
+}
+
+struct Path {
+    points: Vec<Vec3>,
+    closed: bool,
+    widths: Vec<f32>,
+}
+
+impl CommonNode for Sweep {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut consumed = HashSet::new();
+        let mut curves = Vec::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            let found = geometry::curves(mesh);
+            if !found.is_empty() {
+                consumed.insert(idx);
+                let widths = match mesh.attribute(ATTRIBUTE_WIDTH) {
+                    Some(VertexAttributeValues::Float32(widths)) => widths.clone(),
+                    _ => vec![1.0; mesh.count_vertices()],
+                };
+                curves.extend(found.into_iter().map(|curve| Path {
+                    points: curve.points(mesh),
+                    closed: curve.closed,
+                    widths: curve.vertices.iter().map(|&vertex| widths[vertex as usize]).collect(),
+                }));
+            }
+        }
+
+        let profile = match curves.first() {
+            Some(profile) if curves.len() > 1 && profile.points.len() > 1 => profile,
+            _ => return,
+        };
+
+        let surfaces = curves[1..]
+            .iter()
+            .filter(|path| path.points.len() > 1)
+            .flat_map(|path| self.sweep(profile, path))
+            .collect();
+
+        object.remove_meshes(&consumed);
+        if let Some(surface) = Surface::merge(surfaces) {
+            object.push_surface(surface);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Vec2;
+
+    use super::*;
+    use crate::node::{Circle, LSystem};
+
+    #[test]
+    fn sweeps_l_system_branches_at_their_width() {
+        let mut object = ProcessObject::default();
+        Circle::new(1.0, 8).process(&mut object);
+        let mut branches = ProcessObject::default();
+        LSystem::new("F!F")
+            .with_step(1.0, 1.0)
+            .with_width(0.2, 0.5)
+            .process(&mut branches);
+        object.merge(branches);
+
+        Sweep::new().with_caps(false).process(&mut object);
+
+        for position in geometry::positions(&object.meshes[0]) {
+            let radius = Vec2::new(position.x, position.z).length();
+            let expected = if position.y > 1.5 { 0.1 } else { 0.2 };
+            assert!(
+                (radius - expected).abs() < 1e-4,
+                "radius {} at height {}",
+                radius,
+                position.y
+            );
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct SwitchType;
+
+impl TypedNode for Switch {
+    type Type = SwitchType;
+}
+
+/// Passes through only the input child at `index`, clamped to the last input. The other inputs are not cooked.
+#[derive(Default)]
+pub struct Switch {
+    pub index: u32,
+}
+
+impl Switch {
+    pub fn new(index: u32) -> Self {
+        Self { index }
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((SwitchType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Switch {
+    fn process(&self, _object: &mut ProcessObject) {}
+
+    fn active_inputs(&self, count: usize) -> Vec<usize> {
+        if count == 0 {
+            return Vec::new();
+        }
+        vec![(self.index as usize).min(count - 1)]
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,82 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{self, Capture},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct TaperType;
+
+impl TypedNode for Taper {
+    type Type = TaperType;
+}
+
+/// Scales geometry away from the capture axis, blending from `start_scale` at the origin to `end_scale` at the end of
+/// the capture region.
+pub struct Taper {
+    pub capture: Capture,
+    pub start_scale: f32,
+    pub end_scale: f32,
+    pub group: Option<String>,
+}
+
+impl Taper {
+    pub fn new(capture: Capture, end_scale: f32) -> Self {
+        Self {
+            capture,
+            start_scale: 1.0,
+            end_scale,
+            group: None,
+        }
+    }
+
+    pub fn with_start_scale(mut self, start_scale: f32) -> Self {
+        self.start_scale = start_scale;
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((TaperType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn deform(&self, position: Vec3) -> Vec3 {
+        let axis = self.capture.axis();
+        let local = position - self.capture.origin;
+        let along = axis * local.dot(axis);
+        let ratio = self.capture.ratio(position);
+        let scale = self.start_scale + (self.end_scale - self.start_scale) * ratio;
+        self.capture.origin + along + (local - along) * scale
+    }
+}
+
+impl Default for Taper {
+    fn default() -> Self {
+        Self::new(Capture::default(), 0.5)
+    }
+}
+
+impl CommonNode for Taper {
+    fn process(&self, object: &mut ProcessObject) {
+        geometry::deform_selected(object, self.group.as_deref(), |position| self.deform(position));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
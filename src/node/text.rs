@@ -0,0 +1,297 @@
+use std::{any::Any, path::PathBuf};
+
+use bevy::prelude::{Commands, Component, Vec2, Vec3};
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+use crate::{
+    geometry::{self, FillRule, Surface},
+    node::{Finals, Selection},
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct TextType;
+
+impl TypedNode for Text {
+    type Type = TextType;
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum TextAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// Adds the outlines of a string set in a TrueType or OpenType font as polygons in the XY plane, facing +Z with the
+/// baseline of the first line on the X axis. Lines break at `\n` and are aligned around the origin.
+///
+/// With a depth the glyphs are extruded towards -Z, with side walls and a back face. The vertices of every glyph
+/// form a selection group named by `group_prefix` followed by the position of its character in the string.
+pub struct Text {
+    pub font: PathBuf,
+    pub text: String,
+    /// Height of the em square in world units.
+    pub size: f32,
+    pub kerning: bool,
+    pub align: TextAlign,
+    pub depth: f32,
+    /// Segments that every curve of an outline is cut into.
+    pub curve_segments: u32,
+    pub group_prefix: String,
+}
+
+impl Text {
+    pub fn new(font: impl Into<PathBuf>, text: impl Into<String>) -> Self {
+        Self {
+            font: font.into(),
+            text: text.into(),
+            size: 1.0,
+            kerning: true,
+            align: TextAlign::Left,
+            depth: 0.0,
+            curve_segments: 4,
+            group_prefix: "glyph".into(),
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_kerning(mut self, kerning: bool) -> Self {
+        self.kerning = kerning;
+        self
+    }
+
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    pub fn with_depth(mut self, depth: f32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn with_curve_segments(mut self, curve_segments: u32) -> Self {
+        self.curve_segments = curve_segments;
+        self
+    }
+
+    pub fn with_group_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.group_prefix = prefix.into();
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((TextType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    /// Glyphs with their character position and origin.
+    fn layout(&self, face: &Face, scale: f32) -> Vec<(usize, GlyphId, Vec2)> {
+        let line_height = (face.ascender() - face.descender() + face.line_gap()) as f32 * scale;
+        let mut placed = Vec::new();
+        let mut position = 0;
+        for (line_idx, line) in self.text.split('\n').enumerate() {
+            let mut line_glyphs = Vec::new();
+            let mut pen = 0.0;
+            let mut prev = None;
+            for character in line.chars() {
+                let glyph = face.glyph_index(character);
+                if let (Some(left), Some(right), true) = (prev, glyph, self.kerning) {
+                    pen += kerning(face, left, right) * scale;
+                }
+                if let Some(glyph) = glyph {
+                    line_glyphs.push((position, glyph, pen));
+                    pen += face.glyph_hor_advance(glyph).unwrap_or(0) as f32 * scale;
+                }
+                prev = glyph;
+                position += 1;
+            }
+            // the line break is a character of the string too
+            position += 1;
+
+            let offset = match self.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => -pen / 2.0,
+                TextAlign::Right => -pen,
+            };
+            let baseline = -(line_idx as f32) * line_height;
+            placed.extend(
+                line_glyphs
+                    .into_iter()
+                    .map(|(position, glyph, x)| (position, glyph, Vec2::new(x + offset, baseline))),
+            );
+        }
+        placed
+    }
+
+    fn glyph(&self, contours: Vec<Vec<Vec2>>) -> Option<Surface> {
+        let mut surfaces = Vec::new();
+        for (outer, holes) in geometry::nest_loops(contours, FillRule::NonZero) {
+            let front = |ring: &[Vec2]| ring.iter().map(|point| point.extend(0.0)).collect::<Vec<_>>();
+            let back = |ring: &[Vec2]| ring.iter().map(|point| point.extend(-self.depth)).collect::<Vec<_>>();
+            let front_holes = holes.iter().map(|hole| front(hole)).collect::<Vec<_>>();
+            surfaces.push(Surface::cap_with_holes(&front(&outer), &front_holes, Vec3::Z));
+
+            if self.depth > 0.0 {
+                let back_holes = holes.iter().map(|hole| back(hole)).collect::<Vec<_>>();
+                surfaces.push(Surface::cap_with_holes(&back(&outer), &back_holes, -Vec3::Z));
+                for ring in std::iter::once(&outer).chain(&holes) {
+                    surfaces.push(Surface::rings(&[back(ring), front(ring)], true, false, &[0.0, 1.0]));
+                }
+            }
+        }
+        Surface::merge(surfaces)
+    }
+}
+
+impl Default for Text {
+    fn default() -> Self {
+        Self::new("", "Text")
+    }
+}
+
+impl CommonNode for Text {
+    fn process(&self, object: &mut ProcessObject) {
+        let data = match std::fs::read(&self.font) {
+            Ok(data) => data,
+            Err(err) => {
+                eprintln!("Font could not be read from {:?}: {:?}", self.font, err);
+                return;
+            },
+        };
+        let face = match Face::from_slice(&data, 0) {
+            Ok(face) => face,
+            Err(err) => {
+                eprintln!("Font could not be parsed from {:?}: {:?}", self.font, err);
+                return;
+            },
+        };
+        let scale = self.size / face.units_per_em().unwrap_or(1000) as f32;
+
+        let mut glyphs = Vec::new();
+        for (position, glyph, origin) in self.layout(&face, scale) {
+            let mut outline = Outline::new(scale, origin, self.curve_segments.max(1));
+            if face.outline_glyph(glyph, &mut outline).is_none() {
+                continue;
+            }
+            if let Some(surface) = self.glyph(outline.contours) {
+                glyphs.push((position, surface));
+            }
+        }
+
+        let mesh = object.meshes.len();
+        let mut offset = 0;
+        let mut surfaces = Vec::with_capacity(glyphs.len());
+        for (position, surface) in glyphs {
+            let count = surface.mesh.count_vertices() as u32;
+            object
+                .selections
+                .entry(format!("{}{}", self.group_prefix, position))
+                .or_default()
+                .push(Selection {
+                    mesh,
+                    indices: (offset..offset + count).collect(),
+                });
+            offset += count;
+            surfaces.push(surface);
+        }
+
+        if let Some(surface) = Surface::merge(surfaces) {
+            object.push_surface(surface);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> f32 {
+    face.kerning_subtables()
+        .filter(|subtable| subtable.is_horizontal() && !subtable.has_cross_stream() && !subtable.has_state_machine())
+        .filter_map(|subtable| subtable.glyphs_kerning(left, right))
+        .map(f32::from)
+        .sum()
+}
+
+/// Collects glyph contours as polylines, placed and scaled into world units.
+struct Outline {
+    scale: f32,
+    origin: Vec2,
+    segments: u32,
+    contours: Vec<Vec<Vec2>>,
+    current: Vec<Vec2>,
+}
+
+impl Outline {
+    fn new(scale: f32, origin: Vec2, segments: u32) -> Self {
+        Self {
+            scale,
+            origin,
+            segments,
+            contours: Vec::new(),
+            current: Vec::new(),
+        }
+    }
+
+    fn point(&self, x: f32, y: f32) -> Vec2 {
+        self.origin + Vec2::new(x, y) * self.scale
+    }
+
+    fn last(&self) -> Vec2 {
+        self.current.last().copied().unwrap_or(self.origin)
+    }
+}
+
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        let point = self.point(x, y);
+        self.current.push(point);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.point(x, y);
+        self.current.push(point);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (start, control, end) = (self.last(), self.point(x1, y1), self.point(x, y));
+        for step in 1..=self.segments {
+            let t = step as f32 / self.segments as f32;
+            let point = start.lerp(control, t).lerp(control.lerp(end, t), t);
+            self.current.push(point);
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (start, first, second, end) = (self.last(), self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        for step in 1..=self.segments {
+            let t = step as f32 / self.segments as f32;
+            let [a, b, c] = [(start, first), (first, second), (second, end)].map(|(from, to)| from.lerp(to, t));
+            let point = a.lerp(b, t).lerp(b.lerp(c, t), t);
+            self.current.push(point);
+        }
+    }
+
+    fn close(&mut self) {
+        let mut contour = std::mem::take(&mut self.current);
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+        if !contour.is_empty() {
+            self.contours.push(contour);
+        }
+    }
+}
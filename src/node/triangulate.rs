@@ -0,0 +1,65 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct TriangulateType;
+
+impl TypedNode for Triangulate {
+    type Type = TriangulateType;
+}
+
+/// Replaces the n-gon faces of every mesh with triangles cut by ear clipping, which also handles concave faces that
+/// a fan triangulation would fold over. Meshes without faces are already triangles and pass through.
+#[derive(Default)]
+pub struct Triangulate;
+
+impl Triangulate {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((TriangulateType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl CommonNode for Triangulate {
+    fn process(&self, object: &mut ProcessObject) {
+        for (idx, mesh) in object.meshes.iter_mut().enumerate() {
+            let faces = match object.polygons.remove(&idx) {
+                Some(faces) => faces,
+                None => continue,
+            };
+
+            let positions = geometry::positions(mesh);
+            let indices = faces
+                .iter()
+                .flat_map(|face| {
+                    let points = face
+                        .iter()
+                        .map(|&vertex| positions[vertex as usize])
+                        .collect::<Vec<_>>();
+                    geometry::triangulate_polygon(&points)
+                        .into_iter()
+                        .flatten()
+                        .map(|corner| face[corner as usize])
+                })
+                .collect();
+            geometry::set_indices(mesh, indices);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,75 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Quat, Vec3};
+
+use crate::{
+    geometry::{self, Capture},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct TwistType;
+
+impl TypedNode for Twist {
+    type Type = TwistType;
+}
+
+/// Rotates geometry around the capture axis, from no rotation at the origin up to `angle` radians at the end of the
+/// capture region.
+pub struct Twist {
+    pub capture: Capture,
+    pub angle: f32,
+    pub group: Option<String>,
+}
+
+impl Twist {
+    pub fn new(capture: Capture, angle: f32) -> Self {
+        Self {
+            capture,
+            angle,
+            group: None,
+        }
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((TwistType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+
+    fn deform(&self, position: Vec3) -> Vec3 {
+        let rotation = Quat::from_axis_angle(self.capture.axis(), self.angle * self.capture.ratio(position));
+        self.capture.origin + rotation * (position - self.capture.origin)
+    }
+}
+
+impl Default for Twist {
+    fn default() -> Self {
+        Self::new(Capture::default(), std::f32::consts::FRAC_PI_2)
+    }
+}
+
+impl CommonNode for Twist {
+    fn process(&self, object: &mut ProcessObject) {
+        if self.capture.axis() == Vec3::ZERO {
+            return;
+        }
+
+        geometry::deform_selected(object, self.group.as_deref(), |position| self.deform(position));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,66 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{geometry::VolumeOperation, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct VolumeCombineType;
+
+impl TypedNode for VolumeCombine {
+    type Type = VolumeCombineType;
+}
+
+/// Combines every volume after the first into the first one, in order, and removes them. A `smoothness` above 0
+/// blends the shapes into each other over about that distance instead of leaving a crease.
+pub struct VolumeCombine {
+    pub operation: VolumeOperation,
+    pub smoothness: f32,
+}
+
+impl VolumeCombine {
+    pub fn new(operation: VolumeOperation) -> Self {
+        Self {
+            operation,
+            smoothness: 0.0,
+        }
+    }
+
+    pub fn with_smoothness(mut self, smoothness: f32) -> Self {
+        self.smoothness = smoothness;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((VolumeCombineType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for VolumeCombine {
+    fn default() -> Self {
+        Self::new(VolumeOperation::Union)
+    }
+}
+
+impl CommonNode for VolumeCombine {
+    fn process(&self, object: &mut ProcessObject) {
+        if object.volumes.len() < 2 {
+            return;
+        }
+        let others = object.volumes.split_off(1);
+        for other in &others {
+            object.volumes[0] = object.volumes[0].combine(other, self.operation, self.smoothness);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
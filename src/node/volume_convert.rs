@@ -0,0 +1,80 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component};
+
+use crate::{
+    geometry::{self, Surface},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct VolumeConvertType;
+
+impl TypedNode for VolumeConvert {
+    type Type = VolumeConvertType;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Polygonizer {
+    /// Triangles with their vertices on the grid edges, smooth but rounding off sharp features.
+    MarchingCubes,
+    /// Quads with a vertex per grid cell, which keep sharp edges and corners.
+    DualContouring,
+}
+
+/// Turns the volumes into one mesh along the surface where their distance equals `iso`, with normals from the
+/// distance gradient. Raising `iso` grows the shapes and lowering it shrinks them.
+pub struct VolumeConvert {
+    pub polygonizer: Polygonizer,
+    pub iso: f32,
+}
+
+impl VolumeConvert {
+    pub fn new(polygonizer: Polygonizer) -> Self {
+        Self { polygonizer, iso: 0.0 }
+    }
+
+    pub fn with_iso(mut self, iso: f32) -> Self {
+        self.iso = iso;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((VolumeConvertType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for VolumeConvert {
+    fn default() -> Self {
+        Self::new(Polygonizer::MarchingCubes)
+    }
+}
+
+impl CommonNode for VolumeConvert {
+    fn process(&self, object: &mut ProcessObject) {
+        let surfaces = std::mem::take(&mut object.volumes)
+            .iter()
+            .map(|volume| match self.polygonizer {
+                Polygonizer::MarchingCubes => geometry::marching_cubes(volume, self.iso),
+                Polygonizer::DualContouring => geometry::dual_contouring(volume, self.iso),
+            })
+            .filter(|surface| !surface.faces.is_empty())
+            .collect();
+
+        if let Some(surface) = Surface::merge(surfaces) {
+            object.push_surface(surface);
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
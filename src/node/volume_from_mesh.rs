@@ -0,0 +1,72 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::{
+    prelude::{Commands, Component},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::{geometry::Volume, node::Finals, CommonNode, Node, ProcessObject, SpawnedNode, TypedNode};
+
+#[derive(Copy, Clone, Component)]
+pub struct VolumeFromMeshType;
+
+impl TypedNode for VolumeFromMesh {
+    type Type = VolumeFromMeshType;
+}
+
+/// Replaces every triangle mesh with a signed distance volume of it. Meshes should be closed for the inside to be
+/// found reliably.
+pub struct VolumeFromMesh {
+    pub voxel_size: f32,
+    /// Samples added around the bounds of the mesh, so that shapes grown or blended later still fit.
+    pub padding: u32,
+}
+
+impl VolumeFromMesh {
+    pub fn new(voxel_size: f32) -> Self {
+        Self { voxel_size, padding: 3 }
+    }
+
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((VolumeFromMeshType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for VolumeFromMesh {
+    fn default() -> Self {
+        Self::new(0.05)
+    }
+}
+
+impl CommonNode for VolumeFromMesh {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut consumed = HashSet::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+                continue;
+            }
+            let padding = self.padding as f32 * self.voxel_size;
+            if let Some(volume) = Volume::from_mesh(mesh, self.voxel_size, padding) {
+                object.volumes.push(volume);
+                consumed.insert(idx);
+            }
+        }
+        object.remove_meshes(&consumed);
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,99 @@
+use std::any::Any;
+
+use bevy::prelude::{Commands, Component, Vec3};
+
+use crate::{
+    geometry::{SdfPrimitive, Volume},
+    node::Finals,
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct VolumePrimitiveType;
+
+impl TypedNode for VolumePrimitive {
+    type Type = VolumePrimitiveType;
+}
+
+/// Adds a signed distance volume of a primitive shape placed at `center`.
+pub struct VolumePrimitive {
+    pub primitive: SdfPrimitive,
+    pub center: Vec3,
+    pub voxel_size: f32,
+    /// Samples added around the bounds of the shape, so that shapes grown or blended later still fit.
+    pub padding: u32,
+}
+
+impl VolumePrimitive {
+    pub fn new(primitive: SdfPrimitive) -> Self {
+        Self {
+            primitive,
+            center: Vec3::ZERO,
+            voxel_size: 0.05,
+            padding: 3,
+        }
+    }
+
+    pub fn sphere(radius: f32) -> Self {
+        Self::new(SdfPrimitive::Sphere { radius })
+    }
+
+    pub fn cuboid(half_extents: Vec3, radius: f32) -> Self {
+        Self::new(SdfPrimitive::Box { half_extents, radius })
+    }
+
+    pub fn torus(major_radius: f32, minor_radius: f32) -> Self {
+        Self::new(SdfPrimitive::Torus {
+            major_radius,
+            minor_radius,
+        })
+    }
+
+    pub fn with_center(mut self, center: Vec3) -> Self {
+        self.center = center;
+        self
+    }
+
+    pub fn with_voxel_size(mut self, voxel_size: f32) -> Self {
+        self.voxel_size = voxel_size;
+        self
+    }
+
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((VolumePrimitiveType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for VolumePrimitive {
+    fn default() -> Self {
+        Self::sphere(0.5)
+    }
+}
+
+impl CommonNode for VolumePrimitive {
+    fn process(&self, object: &mut ProcessObject) {
+        let extent = self.primitive.extent() + Vec3::splat(self.padding as f32 * self.voxel_size);
+        object.volumes.push(Volume::from_fn(
+            self.center - extent,
+            self.center + extent,
+            self.voxel_size,
+            |point| self.primitive.distance(point - self.center),
+        ));
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
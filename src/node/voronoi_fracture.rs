@@ -0,0 +1,143 @@
+use std::{any::Any, collections::HashSet};
+
+use bevy::{
+    prelude::{Commands, Component},
+    render::mesh::PrimitiveTopology,
+};
+
+use crate::{
+    geometry::{self, Surface, ATTRIBUTE_NAME},
+    node::{Finals, Selection},
+    CommonNode, Node, ProcessObject, SpawnedNode, TypedNode,
+};
+
+#[derive(Copy, Clone, Component)]
+pub struct VoronoiFractureType;
+
+impl TypedNode for VoronoiFracture {
+    type Type = VoronoiFractureType;
+}
+
+/// Shatters every closed triangle mesh into the Voronoi cells around the points of the point meshes, such as the
+/// output of `Scatter`, which are consumed as seeds. Each mesh becomes one mesh of pieces, with every piece numbered
+/// in [`ATTRIBUTE_NAME`] over the whole object and the faces exposed by the cuts in the `inside_group` selection.
+///
+/// Without any seed points the node leaves the object untouched. Only positions, normals and UVs are carried over to
+/// the pieces. With a group prefix, each piece also becomes a selection group named by the prefix followed by its
+/// number.
+pub struct VoronoiFracture {
+    pub inside_group: String,
+    pub group_prefix: Option<String>,
+}
+
+impl VoronoiFracture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_inside_group(mut self, group: impl Into<String>) -> Self {
+        self.inside_group = group.into();
+        self
+    }
+
+    pub fn with_groups(mut self, prefix: impl Into<String>) -> Self {
+        self.group_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn spawn(self, commands: &mut Commands) -> SpawnedNode {
+        let id = commands
+            .spawn_bundle((VoronoiFractureType, Node(Box::new(self)), Finals::default()))
+            .id();
+        SpawnedNode { id }
+    }
+}
+
+impl Default for VoronoiFracture {
+    fn default() -> Self {
+        Self {
+            inside_group: "inside".to_string(),
+            group_prefix: None,
+        }
+    }
+}
+
+impl CommonNode for VoronoiFracture {
+    fn process(&self, object: &mut ProcessObject) {
+        let mut seeds = Vec::new();
+        let mut removed = HashSet::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            if mesh.primitive_topology() == PrimitiveTopology::PointList {
+                seeds.extend(geometry::positions(mesh));
+                removed.insert(idx);
+            }
+        }
+        if seeds.is_empty() {
+            return;
+        }
+
+        let mut fractured = Vec::new();
+        for (idx, mesh) in object.meshes.iter().enumerate() {
+            if mesh.primitive_topology() == PrimitiveTopology::TriangleList {
+                fractured.push(geometry::voronoi_fracture(mesh, &seeds));
+                removed.insert(idx);
+            }
+        }
+        object.remove_meshes(&removed);
+
+        let mut piece: u32 = 0;
+        for fragments in fractured {
+            let mut meshes = Vec::with_capacity(fragments.len());
+            let mut inside = Vec::new();
+            let mut pieces = Vec::with_capacity(fragments.len());
+            let mut offset = 0;
+            for mut fragment in fragments {
+                let count = fragment.mesh.count_vertices() as u32;
+                fragment
+                    .mesh
+                    .insert_attribute(ATTRIBUTE_NAME, vec![piece; count as usize]);
+                inside.extend(fragment.inside.iter().map(|vertex| vertex + offset));
+                pieces.push((piece, offset..offset + count));
+                meshes.push(fragment.mesh);
+                offset += count;
+                piece += 1;
+            }
+            let mesh = match geometry::merge(&meshes) {
+                Some(mesh) => mesh,
+                None => continue,
+            };
+
+            let faces = geometry::triangles(&mesh)
+                .iter()
+                .map(|triangle| triangle.to_vec())
+                .collect();
+            object.push_surface(Surface { mesh, faces });
+            let mesh = object.meshes.len() - 1;
+            object
+                .selections
+                .entry(self.inside_group.clone())
+                .or_default()
+                .push(Selection { mesh, indices: inside });
+            if let Some(prefix) = &self.group_prefix {
+                for (piece, vertices) in pieces {
+                    object
+                        .selections
+                        .entry(format!("{}{}", prefix, piece))
+                        .or_default()
+                        .push(Selection {
+                            mesh,
+                            indices: vertices.collect(),
+                        });
+                }
+            }
+        }
+    }
+
+    fn as_any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
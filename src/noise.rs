@@ -0,0 +1,264 @@
+use bevy::prelude::Vec3;
+
+use crate::geometry::Random;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NoiseBasis {
+    Perlin,
+    Simplex,
+    /// Distance to the nearest jittered feature point (F1 cellular noise).
+    Worley,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fractal {
+    None,
+    /// Fractional Brownian motion: octaves summed with decreasing amplitude, in `[-1, 1]`.
+    Fbm,
+    /// Inverted absolute octaves which form sharp crests, in `[0, 1]`.
+    Ridged,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct NoiseSettings {
+    pub basis: NoiseBasis,
+    pub fractal: Fractal,
+    pub frequency: f32,
+    pub amplitude: f32,
+    pub octaves: u32,
+    pub lacunarity: f32,
+    pub gain: f32,
+    pub offset: Vec3,
+    pub seed: u64,
+}
+
+impl NoiseSettings {
+    pub fn sampler(&self) -> NoiseSampler {
+        NoiseSampler::new(*self)
+    }
+}
+
+impl Default for NoiseSettings {
+    fn default() -> Self {
+        Self {
+            basis: NoiseBasis::Perlin,
+            fractal: Fractal::Fbm,
+            frequency: 1.0,
+            amplitude: 1.0,
+            octaves: 4,
+            lacunarity: 2.0,
+            gain: 0.5,
+            offset: Vec3::ZERO,
+            seed: 0,
+        }
+    }
+}
+
+pub struct NoiseSampler {
+    settings: NoiseSettings,
+    perm: [u8; 512],
+}
+
+impl NoiseSampler {
+    pub fn new(settings: NoiseSettings) -> Self {
+        let mut table = [0u8; 256];
+        for (idx, value) in table.iter_mut().enumerate() {
+            *value = idx as u8;
+        }
+
+        let mut random = Random::new(settings.seed);
+        for idx in (1..table.len()).rev() {
+            let other = random.next_u32() as usize % (idx + 1);
+            table.swap(idx, other);
+        }
+
+        let mut perm = [0u8; 512];
+        for (idx, value) in perm.iter_mut().enumerate() {
+            *value = table[idx & 255];
+        }
+
+        Self { settings, perm }
+    }
+
+    /// Noise value at `position` scaled by the amplitude.
+    pub fn sample(&self, position: Vec3) -> f32 {
+        let NoiseSettings {
+            fractal,
+            frequency,
+            amplitude,
+            octaves,
+            lacunarity,
+            gain,
+            offset,
+            ..
+        } = self.settings;
+        let position = position * frequency + offset;
+
+        let value = match fractal {
+            Fractal::None => self.basis(position),
+            Fractal::Fbm | Fractal::Ridged => {
+                let mut total = 0.0;
+                let mut norm = 0.0;
+                let mut octave_amplitude = 1.0;
+                let mut octave_frequency = 1.0;
+                for _ in 0..octaves.max(1) {
+                    let octave = self.basis(position * octave_frequency);
+                    total += octave_amplitude
+                        * match fractal {
+                            Fractal::Ridged => (1.0 - octave.abs()).powi(2),
+                            _ => octave,
+                        };
+                    norm += octave_amplitude;
+                    octave_amplitude *= gain;
+                    octave_frequency *= lacunarity;
+                }
+                total / norm
+            },
+        };
+        value * amplitude
+    }
+
+    /// Three decorrelated samples, for displacing in 3D rather than along a single direction.
+    pub fn sample_vec3(&self, position: Vec3) -> Vec3 {
+        Vec3::new(
+            self.sample(position),
+            self.sample(position + Vec3::new(31.416, -47.853, 12.793)),
+            self.sample(position + Vec3::new(-23.719, 7.071, 59.183)),
+        )
+    }
+
+    fn basis(&self, position: Vec3) -> f32 {
+        match self.settings.basis {
+            NoiseBasis::Perlin => self.perlin(position),
+            NoiseBasis::Simplex => self.simplex(position),
+            NoiseBasis::Worley => self.worley(position),
+        }
+    }
+
+    fn hash(&self, x: i32, y: i32, z: i32) -> u8 {
+        let perm = &self.perm;
+        perm[perm[perm[(x & 255) as usize] as usize + (y & 255) as usize] as usize + (z & 255) as usize]
+    }
+
+    /// Improved Perlin noise (Perlin 2002).
+    fn perlin(&self, position: Vec3) -> f32 {
+        let cell = position.floor();
+        let [x, y, z] = [cell.x as i32, cell.y as i32, cell.z as i32];
+        let local = position - cell;
+        let [u, v, w] = [fade(local.x), fade(local.y), fade(local.z)];
+
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            gradient(
+                self.hash(x + dx, y + dy, z + dz),
+                local - Vec3::new(dx as f32, dy as f32, dz as f32),
+            )
+        };
+
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 0), corner(1, 0, 0)),
+                lerp(u, corner(0, 1, 0), corner(1, 1, 0)),
+            ),
+            lerp(
+                v,
+                lerp(u, corner(0, 0, 1), corner(1, 0, 1)),
+                lerp(u, corner(0, 1, 1), corner(1, 1, 1)),
+            ),
+        )
+    }
+
+    /// 3D simplex noise following Gustavson's reference implementation.
+    fn simplex(&self, position: Vec3) -> f32 {
+        const SKEW: f32 = 1.0 / 3.0;
+        const UNSKEW: f32 = 1.0 / 6.0;
+
+        let skewed = (position + Vec3::splat((position.x + position.y + position.z) * SKEW)).floor();
+        let [i, j, k] = [skewed.x as i32, skewed.y as i32, skewed.z as i32];
+        let origin = skewed - Vec3::splat((skewed.x + skewed.y + skewed.z) * UNSKEW);
+        let d0 = position - origin;
+
+        let (first, second) = if d0.x >= d0.y {
+            if d0.y >= d0.z {
+                ([1, 0, 0], [1, 1, 0])
+            } else if d0.x >= d0.z {
+                ([1, 0, 0], [1, 0, 1])
+            } else {
+                ([0, 0, 1], [1, 0, 1])
+            }
+        } else if d0.y < d0.z {
+            ([0, 0, 1], [0, 1, 1])
+        } else if d0.x < d0.z {
+            ([0, 1, 0], [0, 1, 1])
+        } else {
+            ([0, 1, 0], [1, 1, 0])
+        };
+
+        let to_vec = |corner: [i32; 3]| Vec3::new(corner[0] as f32, corner[1] as f32, corner[2] as f32);
+        let corners = [
+            ([0, 0, 0], d0),
+            (first, d0 - to_vec(first) + Vec3::splat(UNSKEW)),
+            (second, d0 - to_vec(second) + Vec3::splat(2.0 * UNSKEW)),
+            ([1, 1, 1], d0 - Vec3::ONE + Vec3::splat(3.0 * UNSKEW)),
+        ];
+
+        let total = corners
+            .iter()
+            .map(|(corner, offset)| {
+                let falloff = 0.6 - offset.length_squared();
+                if falloff < 0.0 {
+                    0.0
+                } else {
+                    falloff.powi(4) * gradient(self.hash(i + corner[0], j + corner[1], k + corner[2]), *offset)
+                }
+            })
+            .sum::<f32>();
+        32.0 * total
+    }
+
+    /// F1 cellular noise remapped from `[0, 1]` distances to `[-1, 1]`.
+    fn worley(&self, position: Vec3) -> f32 {
+        let cell = position.floor();
+        let [x, y, z] = [cell.x as i32, cell.y as i32, cell.z as i32];
+
+        let mut nearest = f32::MAX;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let [cx, cy, cz] = [x + dx, y + dy, z + dz];
+                    let jitter = Vec3::new(
+                        self.hash(cx, cy, cz) as f32,
+                        self.hash(cx + 101, cy, cz) as f32,
+                        self.hash(cx, cy + 211, cz) as f32,
+                    ) / 255.0;
+                    let feature = Vec3::new(cx as f32, cy as f32, cz as f32) + jitter;
+                    nearest = nearest.min(feature.distance_squared(position));
+                }
+            }
+        }
+        (nearest.sqrt() * 2.0 - 1.0).clamp(-1.0, 1.0)
+    }
+}
+
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f32, a: f32, b: f32) -> f32 {
+    a + t * (b - a)
+}
+
+/// Dot product of `offset` with one of the twelve cube edge directions picked by `hash`.
+fn gradient(hash: u8, offset: Vec3) -> f32 {
+    let h = hash & 15;
+    let u = if h < 8 { offset.x } else { offset.y };
+    let v = if h < 4 {
+        offset.y
+    } else if h == 12 || h == 14 {
+        offset.x
+    } else {
+        offset.z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
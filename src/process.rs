@@ -1,12 +1,16 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use bevy::prelude::{
-    Assets, Children, Commands, Component, Entity, GlobalTransform, Mesh, PbrBundle, Query, ResMut, StandardMaterial,
-    Transform, With,
+use bevy::{
+    prelude::{
+        Assets, Children, Color, Commands, Component, Entity, GlobalTransform, Mesh, PbrBundle, Query, ResMut,
+        StandardMaterial, Transform, With,
+    },
+    render::mesh::PrimitiveTopology,
 };
 
 use crate::{
-    node::{FinalType, Finals, Selection},
+    geometry,
+    node::{FinalLines, FinalType, Finals, LodMeshes, Lods, Parameters, Selection, SubnetScope},
     CommonNode, Node,
 };
 
@@ -32,16 +36,196 @@ impl PbrState {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ProcessObject {
     pub meshes: Vec<Mesh>,
     pub selections: HashMap<String, Vec<Selection>>,
+    /// N-gon faces of a mesh, keyed by mesh index, as loops of vertex indices. The mesh indices then hold a
+    /// triangulation of these faces; nodes that rebuild a mesh's triangles without keeping its faces drop its entry.
+    pub polygons: HashMap<usize, Vec<Vec<u32>>>,
+    /// Parametric curves, which become line meshes through `Resample`.
+    pub splines: Vec<geometry::Spline>,
+    /// Terrain heightfields, which become meshes through `HeightfieldConvert`.
+    pub heightfields: Vec<geometry::Heightfield>,
+    /// Signed distance volumes, which become meshes through `VolumeConvert`.
+    pub volumes: Vec<geometry::Volume>,
     pub materials: Vec<StandardMaterial>,
+    /// Promoted parameters of the subnet instance being cooked.
+    pub parameters: Parameters,
     pub transform: Option<Transform>,
     pub global_transform: Option<GlobalTransform>,
 }
 
 impl ProcessObject {
+    pub fn selected_vertices(&self, group: &str, mesh: usize) -> HashSet<u32> {
+        self.selections
+            .get(group)
+            .into_iter()
+            .flatten()
+            .filter(|selection| selection.mesh == mesh)
+            .flat_map(|selection| selection.indices.iter().copied())
+            .collect()
+    }
+
+    /// Removes the meshes at the given positions, renumbering the selections and faces of the meshes after them.
+    pub fn remove_meshes(&mut self, removed: &HashSet<usize>) {
+        let mut renumbered = Vec::with_capacity(self.meshes.len());
+        let mut next = 0;
+        for idx in 0..self.meshes.len() {
+            if removed.contains(&idx) {
+                renumbered.push(None);
+            } else {
+                renumbered.push(Some(next));
+                next += 1;
+            }
+        }
+
+        let mut idx = 0;
+        self.meshes.retain(|_| {
+            idx += 1;
+            !removed.contains(&(idx - 1))
+        });
+
+        for selections in self.selections.values_mut() {
+            selections.retain_mut(|selection| match renumbered.get(selection.mesh).copied().flatten() {
+                Some(mesh) => {
+                    selection.mesh = mesh;
+                    true
+                },
+                None => false,
+            });
+        }
+        self.selections.retain(|_, selections| !selections.is_empty());
+
+        self.polygons = std::mem::take(&mut self.polygons)
+            .into_iter()
+            .filter_map(|(mesh, faces)| Some((renumbered.get(mesh).copied().flatten()?, faces)))
+            .collect();
+    }
+
+    /// Appends a generated surface as a new mesh along with its faces.
+    pub fn push_surface(&mut self, surface: geometry::Surface) {
+        self.polygons.insert(self.meshes.len(), surface.faces);
+        self.meshes.push(surface.mesh);
+    }
+
+    /// Takes out the line meshes, which are drawn as entities of their own next to the first remaining mesh. When
+    /// there is nothing but lines, the first line mesh stays in place.
+    pub fn take_lines(&mut self) -> Vec<Mesh> {
+        let is_line = |mesh: &Mesh| {
+            matches!(
+                mesh.primitive_topology(),
+                PrimitiveTopology::LineList | PrimitiveTopology::LineStrip
+            )
+        };
+        let mut removed = self
+            .meshes
+            .iter()
+            .enumerate()
+            .filter(|(_, mesh)| is_line(mesh))
+            .map(|(idx, _)| idx)
+            .collect::<HashSet<_>>();
+        if removed.len() == self.meshes.len() {
+            removed.remove(&0);
+        }
+
+        let lines = self
+            .meshes
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| removed.contains(idx))
+            .map(|(_, mesh)| mesh.clone())
+            .collect();
+        self.remove_meshes(&removed);
+        lines
+    }
+
+    /// Merges the meshes of `other` into the meshes at the same position, shifting its selections and faces along.
+    /// Meshes that cannot be merged are appended, and so are splines, heightfields and volumes. Materials,
+    /// parameters and transforms are only taken when missing here.
+    pub fn merge(&mut self, other: ProcessObject) {
+        let ProcessObject {
+            meshes,
+            selections,
+            polygons,
+            splines,
+            heightfields,
+            volumes,
+            materials,
+            parameters,
+            transform,
+            global_transform,
+        } = other;
+
+        self.splines.extend(splines);
+        self.heightfields.extend(heightfields);
+        self.volumes.extend(volumes);
+
+        let mut polygons = polygons;
+        let mut placement = Vec::with_capacity(meshes.len());
+        for (idx, mesh) in meshes.into_iter().enumerate() {
+            let faces = polygons.remove(&idx);
+            let merged = self.meshes.get(idx).and_then(|existing| {
+                let offset = existing.count_vertices() as u32;
+                Some((offset, geometry::merge(&[existing.clone(), mesh.clone()])?))
+            });
+
+            match merged {
+                Some((offset, merged)) => {
+                    // faces have to cover the whole mesh, so a side without them contributes its triangles
+                    if faces.is_some() || self.polygons.contains_key(&idx) {
+                        let as_faces = |mesh: &Mesh| {
+                            geometry::triangles(mesh)
+                                .into_iter()
+                                .map(|tri| tri.to_vec())
+                                .collect::<Vec<_>>()
+                        };
+                        let mut existing = self
+                            .polygons
+                            .remove(&idx)
+                            .unwrap_or_else(|| as_faces(&self.meshes[idx]));
+                        existing.extend(
+                            faces
+                                .unwrap_or_else(|| as_faces(&mesh))
+                                .into_iter()
+                                .map(|face| face.into_iter().map(|vertex| vertex + offset).collect()),
+                        );
+                        self.polygons.insert(idx, existing);
+                    }
+                    self.meshes[idx] = merged;
+                    placement.push((idx, offset));
+                },
+                None => {
+                    self.meshes.push(mesh);
+                    if let Some(faces) = faces {
+                        self.polygons.insert(self.meshes.len() - 1, faces);
+                    }
+                    placement.push((self.meshes.len() - 1, 0));
+                },
+            }
+        }
+
+        for (group, group_selections) in selections {
+            let target = self.selections.entry(group).or_default();
+            for Selection { mesh, indices } in group_selections {
+                let (mesh, offset) = placement[mesh];
+                target.push(Selection {
+                    mesh,
+                    indices: indices.into_iter().map(|vertex| vertex + offset).collect(),
+                });
+            }
+        }
+
+        if self.materials.is_empty() {
+            self.materials = materials;
+        }
+        if self.parameters.0.is_empty() {
+            self.parameters = parameters;
+        }
+        self.transform = self.transform.or(transform);
+        self.global_transform = self.global_transform.or(global_transform);
+    }
+
     pub fn into_pbr(
         self,
         asset_meshes: &mut ResMut<Assets<Mesh>>,
@@ -50,7 +234,12 @@ impl ProcessObject {
         let ProcessObject {
             meshes,
             selections: _,
+            polygons: _,
+            splines: _,
+            heightfields: _,
+            volumes: _,
             materials,
+            parameters: _,
             transform,
             global_transform,
         } = self;
@@ -75,47 +264,131 @@ impl ProcessObject {
     }
 }
 
+type FinalItem<'a> = (
+    Entity,
+    &'a PbrState,
+    &'a Children,
+    Option<&'a Lods>,
+    Option<&'a LodMeshes>,
+    Option<&'a FinalLines>,
+);
+
+type NodeItem = (&'static Node, Option<&'static Children>);
+
 pub fn finalize(
     mut commands: Commands,
-    final_query: Query<(Entity, &PbrState, &Children), With<FinalType>>,
-    mut node_query: Query<(&Node, &mut Finals, Option<&Children>)>,
+    final_query: Query<FinalItem, With<FinalType>>,
+    node_query: Query<NodeItem>,
+    mut finals_query: Query<&mut Finals>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    for (final_id, pbr_state, inputs) in final_query.iter() {
+    for (final_id, pbr_state, inputs, lods, lod_meshes, final_lines) in final_query.iter() {
         if pbr_state.need_calculate() {
-            let mut final_entity = commands.entity(final_id);
             let mut object = ProcessObject::default();
 
+            // cooking registers the final again on every node it still depends on, so branches that were switched
+            // off no longer trigger it
+            for mut finals in finals_query.iter_mut() {
+                finals.0.remove(&final_id);
+            }
+
+            let mut context = CookContext {
+                nodes: &node_query,
+                reached: HashSet::new(),
+                scopes: Vec::new(),
+            };
             for input_id in inputs.iter() {
-                process_input(final_id, *input_id, &mut node_query, &mut object);
+                context.cook(*input_id, &mut object);
+            }
+            for node in context.reached {
+                if let Ok(mut finals) = finals_query.get_mut(node) {
+                    finals.0.insert(final_id);
+                }
+            }
+
+            let lines = object.take_lines();
+            let line_color = object
+                .materials
+                .first()
+                .map_or(Color::WHITE, |material| material.base_color);
+            let (key, levels) = lods
+                .map(|lods| lods.cook(object.meshes.first(), lod_meshes, &mut meshes))
+                .unwrap_or_default();
+            let pbr = object.into_pbr(&mut meshes, &mut materials);
+
+            for line in final_lines.iter().flat_map(|final_lines| &final_lines.0) {
+                commands.entity(*line).despawn();
+            }
+            let line_material = materials.add(StandardMaterial {
+                base_color: line_color,
+                unlit: true,
+                ..Default::default()
+            });
+            let lines = lines
+                .into_iter()
+                .map(|mesh| {
+                    commands
+                        .spawn_bundle(PbrBundle {
+                            mesh: meshes.add(mesh),
+                            material: line_material.clone(),
+                            transform: pbr.transform,
+                            global_transform: pbr.global_transform,
+                            ..Default::default()
+                        })
+                        .id()
+                })
+                .collect();
+
+            let mut final_entity = commands.entity(final_id);
+            if levels.is_empty() {
+                final_entity.remove::<LodMeshes>();
+            } else {
+                final_entity.insert(LodMeshes {
+                    base: pbr.mesh.clone(),
+                    levels,
+                    key,
+                });
             }
 
             final_entity
-                .insert_bundle(object.into_pbr(&mut meshes, &mut materials))
+                .insert_bundle(pbr)
+                .insert(FinalLines(lines))
                 .insert(PbrState::Calculated);
         }
     }
 }
 
-fn process_input(
-    final_id: Entity,
-    input_id: Entity,
-    query: &mut Query<(&Node, &mut Finals, Option<&Children>)>,
-    object: &mut ProcessObject,
-) {
-    let mut finals = query.get_component_mut::<Finals>(input_id).unwrap();
-    if !finals.0.contains(&final_id) {
-        finals.0.insert(final_id);
+/// Access to the graph while a final cooks, for nodes that evaluate other parts of the graph themselves through
+/// [`CommonNode::cook`].
+pub struct CookContext<'a, 'w, 's> {
+    nodes: &'a Query<'w, 's, NodeItem>,
+    /// Nodes cooked so far, which the final is registered on afterwards.
+    reached: HashSet<Entity>,
+    /// Subnet instances whose definitions are being cooked, innermost last.
+    pub scopes: Vec<SubnetScope>,
+}
+
+impl<'a, 'w, 's> CookContext<'a, 'w, 's> {
+    pub fn node(&self, id: Entity) -> Option<&'a Node> {
+        let nodes = self.nodes;
+        nodes.get(id).ok().map(|(node, _)| node)
     }
 
-    let inputs = query.get_component::<Children>(input_id);
-    if let Ok(inputs) = inputs.map(|inputs| inputs.iter().copied().collect::<Vec<_>>()) {
-        for input_id in inputs {
-            process_input(final_id, input_id, query, object);
+    /// Cooks the node `id` after its active inputs into `object`.
+    pub fn cook(&mut self, id: Entity, object: &mut ProcessObject) {
+        let nodes = self.nodes;
+        let (node, inputs) = match nodes.get(id) {
+            Ok((node, inputs)) => (node, inputs.map(|inputs| inputs.to_vec()).unwrap_or_default()),
+            Err(_) => return,
+        };
+        self.reached.insert(id);
+
+        for idx in node.active_inputs(inputs.len()) {
+            self.cook(inputs[idx], object);
+        }
+        if !node.cook(object, &inputs, self) {
+            node.process(object);
         }
     }
-
-    let node = query.get_component::<Node>(input_id).unwrap();
-    node.process(object);
 }
@@ -0,0 +1,738 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::{math::Affine2, prelude::Vec2};
+
+use crate::geometry::FillRule;
+
+/// Outline of one drawn SVG element, flattened into polylines in document units with y pointing down.
+#[derive(Clone, Debug)]
+pub struct SvgShape {
+    pub id: Option<String>,
+    pub subpaths: Vec<Subpath>,
+    pub fill_rule: FillRule,
+    /// False when the element has `fill="none"`.
+    pub filled: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Subpath {
+    pub points: Vec<Vec2>,
+    pub closed: bool,
+    /// Control points of the subpath as a chain of cubic Bezier segments when it has Bezier curves in it, with lines
+    /// and flattened arcs as straight segments. Empty for subpaths without curves.
+    pub curve: Vec<Vec2>,
+}
+
+/// Reads the paths and basic shapes of an SVG document with their transforms applied, cutting every curve into
+/// `curve_segments` segments and arcs into as many per quarter turn. Subpaths with Bezier curves also keep their
+/// control points. Elements inside `defs` and similar containers
+/// are not drawn, and styling beyond the fill is ignored.
+pub fn parse(source: &str, curve_segments: u32) -> Vec<SvgShape> {
+    let segments = curve_segments.max(1);
+    let mut shapes = Vec::new();
+    let mut stack = vec![Style::default()];
+
+    for tag in tags(source) {
+        if tag.closing {
+            if stack.len() > 1 {
+                stack.pop();
+            }
+            continue;
+        }
+
+        let style = stack.last().unwrap().inherit(&tag);
+        if !style.hidden {
+            if let Some(data) = path_data(&tag) {
+                let subpaths = flatten(&data, segments)
+                    .into_iter()
+                    .map(|subpath| Subpath {
+                        points: subpath
+                            .points
+                            .into_iter()
+                            .map(|point| style.transform.transform_point2(point))
+                            .collect(),
+                        closed: subpath.closed,
+                        curve: subpath
+                            .curve
+                            .into_iter()
+                            .map(|point| style.transform.transform_point2(point))
+                            .collect(),
+                    })
+                    .collect::<Vec<_>>();
+                if !subpaths.is_empty() {
+                    shapes.push(SvgShape {
+                        id: tag.attribute("id").map(String::from),
+                        subpaths,
+                        fill_rule: style.fill_rule,
+                        filled: style.filled,
+                    });
+                }
+            }
+        }
+
+        if !tag.self_closing {
+            stack.push(style);
+        }
+    }
+    shapes
+}
+
+#[derive(Clone)]
+struct Style {
+    transform: Affine2,
+    fill_rule: FillRule,
+    filled: bool,
+    hidden: bool,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            transform: Affine2::IDENTITY,
+            fill_rule: FillRule::NonZero,
+            filled: true,
+            hidden: false,
+        }
+    }
+}
+
+impl Style {
+    fn inherit(&self, tag: &Tag) -> Self {
+        let mut style = self.clone();
+        if let Some(transform) = tag.attribute("transform") {
+            style.transform = style.transform * parse_transform(transform);
+        }
+        if let Some(fill_rule) = tag.property("fill-rule") {
+            style.fill_rule = match fill_rule.trim() {
+                "evenodd" => FillRule::EvenOdd,
+                _ => FillRule::NonZero,
+            };
+        }
+        if let Some(fill) = tag.property("fill") {
+            style.filled = fill.trim() != "none";
+        }
+        style.hidden |= matches!(
+            tag.name,
+            "defs" | "clipPath" | "mask" | "symbol" | "pattern" | "marker" | "linearGradient" | "radialGradient"
+        );
+        style
+    }
+}
+
+struct Tag<'a> {
+    name: &'a str,
+    attributes: Vec<(&'a str, &'a str)>,
+    closing: bool,
+    self_closing: bool,
+}
+
+impl<'a> Tag<'a> {
+    fn attribute(&self, name: &str) -> Option<&'a str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| *attribute == name)
+            .map(|(_, value)| *value)
+    }
+
+    /// Presentation property from the `style` attribute, which wins over the attribute of the same name.
+    fn property(&self, name: &str) -> Option<&'a str> {
+        let from_style = self.attribute("style").and_then(|style| {
+            style.split(';').find_map(|declaration| {
+                let (property, value) = declaration.split_once(':')?;
+                (property.trim() == name).then_some(value)
+            })
+        });
+        from_style.or_else(|| self.attribute(name))
+    }
+
+    fn number(&self, name: &str) -> f32 {
+        self.attribute(name)
+            .and_then(|value| Numbers::new(value).next())
+            .unwrap_or(0.0)
+    }
+}
+
+/// Element tags of an XML document in order, skipping comments, declarations and text.
+fn tags(source: &str) -> Vec<Tag<'_>> {
+    let mut tags = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        if rest.starts_with("<!--") {
+            rest = skip_to(rest, "-->");
+            continue;
+        }
+        if rest.starts_with("<![CDATA[") {
+            rest = skip_to(rest, "]]>");
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = skip_to(rest, ">");
+            continue;
+        }
+
+        let closing = rest.starts_with("</");
+        rest = &rest[if closing { 2 } else { 1 }..];
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = &rest[name_end..];
+
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() {
+                break;
+            }
+            if let Some(after) = rest.strip_prefix("/>") {
+                self_closing = true;
+                rest = after;
+                break;
+            }
+            if let Some(after) = rest.strip_prefix('>') {
+                rest = after;
+                break;
+            }
+
+            let attribute_end = rest
+                .find(|c: char| c == '=' || c.is_whitespace() || c == '>' || c == '/')
+                .unwrap_or(rest.len())
+                .max(1);
+            let attribute = &rest[..attribute_end];
+            rest = rest[attribute_end..].trim_start();
+            let value = match rest.strip_prefix('=') {
+                Some(after) => {
+                    let after = after.trim_start();
+                    match after.chars().next() {
+                        Some(quote @ ('"' | '\'')) => {
+                            let value_end = after[1..].find(quote).map_or(after.len(), |idx| idx + 1);
+                            rest = after.get(value_end + 1..).unwrap_or("");
+                            &after[1..value_end]
+                        },
+                        _ => {
+                            let value_end = after
+                                .find(|c: char| c.is_whitespace() || c == '>')
+                                .unwrap_or(after.len());
+                            rest = &after[value_end..];
+                            &after[..value_end]
+                        },
+                    }
+                },
+                None => "",
+            };
+            attributes.push((attribute, value));
+        }
+
+        tags.push(Tag {
+            name,
+            attributes,
+            closing,
+            self_closing,
+        });
+    }
+    tags
+}
+
+fn skip_to<'a>(rest: &'a str, end: &str) -> &'a str {
+    rest.find(end).map_or("", |idx| &rest[idx + end.len()..])
+}
+
+/// Path data of the element, with basic shapes written out as paths.
+fn path_data(tag: &Tag) -> Option<String> {
+    let points = |closed: bool| {
+        let numbers = Numbers::new(tag.attribute("points")?).collect::<Vec<_>>();
+        let mut data = numbers
+            .chunks_exact(2)
+            .enumerate()
+            .map(|(idx, pair)| format!("{} {} {} ", if idx == 0 { 'M' } else { 'L' }, pair[0], pair[1]))
+            .collect::<String>();
+        if closed {
+            data.push('Z');
+        }
+        Some(data)
+    };
+
+    match tag.name {
+        "path" => tag.attribute("d").map(String::from),
+        "polyline" => points(false),
+        "polygon" => points(true),
+        "line" => Some(format!(
+            "M {} {} L {} {}",
+            tag.number("x1"),
+            tag.number("y1"),
+            tag.number("x2"),
+            tag.number("y2")
+        )),
+        "circle" => Some(ellipse(
+            tag.number("cx"),
+            tag.number("cy"),
+            tag.number("r"),
+            tag.number("r"),
+        )),
+        "ellipse" => Some(ellipse(
+            tag.number("cx"),
+            tag.number("cy"),
+            tag.number("rx"),
+            tag.number("ry"),
+        )),
+        "rect" => {
+            let (x, y, width, height) = (
+                tag.number("x"),
+                tag.number("y"),
+                tag.number("width"),
+                tag.number("height"),
+            );
+            let (rx, ry) = match (tag.attribute("rx"), tag.attribute("ry")) {
+                (None, None) => (0.0, 0.0),
+                (Some(_), None) => (tag.number("rx"), tag.number("rx")),
+                (None, Some(_)) => (tag.number("ry"), tag.number("ry")),
+                (Some(_), Some(_)) => (tag.number("rx"), tag.number("ry")),
+            };
+            let (rx, ry) = (rx.clamp(0.0, width / 2.0), ry.clamp(0.0, height / 2.0));
+            Some(if rx > 0.0 && ry > 0.0 {
+                format!(
+                    "M {x0} {y} H {x1} A {rx} {ry} 0 0 1 {x2} {y0} V {y1} A {rx} {ry} 0 0 1 {x1} {y2} H {x0} \
+                     A {rx} {ry} 0 0 1 {x} {y1} V {y0} A {rx} {ry} 0 0 1 {x0} {y} Z",
+                    x0 = x + rx,
+                    x1 = x + width - rx,
+                    x2 = x + width,
+                    y0 = y + ry,
+                    y1 = y + height - ry,
+                    y2 = y + height,
+                )
+            } else {
+                format!("M {} {} H {} V {} H {} Z", x, y, x + width, y + height, x)
+            })
+        },
+        _ => None,
+    }
+}
+
+fn ellipse(cx: f32, cy: f32, rx: f32, ry: f32) -> String {
+    format!(
+        "M {} {cy} A {rx} {ry} 0 1 1 {} {cy} A {rx} {ry} 0 1 1 {} {cy} Z",
+        cx + rx,
+        cx - rx,
+        cx + rx
+    )
+}
+
+/// Applies the transform list of a `transform` attribute, leftmost outermost.
+fn parse_transform(value: &str) -> Affine2 {
+    let mut transform = Affine2::IDENTITY;
+    let mut rest = value;
+    while let Some(open) = rest.find('(') {
+        let name = rest[..open].trim_matches(|c: char| c.is_whitespace() || c == ',');
+        let close = rest[open..].find(')').map_or(rest.len(), |idx| open + idx);
+        let arguments = Numbers::new(&rest[open + 1..close]).collect::<Vec<_>>();
+        rest = rest.get(close + 1..).unwrap_or("");
+
+        let argument = |idx: usize, default: f32| arguments.get(idx).copied().unwrap_or(default);
+        let next = match name {
+            "matrix" if arguments.len() == 6 => Affine2::from_cols_array(&[
+                arguments[0],
+                arguments[1],
+                arguments[2],
+                arguments[3],
+                arguments[4],
+                arguments[5],
+            ]),
+            "translate" => Affine2::from_translation(Vec2::new(argument(0, 0.0), argument(1, 0.0))),
+            "scale" => Affine2::from_scale(Vec2::new(argument(0, 1.0), argument(1, argument(0, 1.0)))),
+            "rotate" => {
+                let center = Vec2::new(argument(1, 0.0), argument(2, 0.0));
+                Affine2::from_translation(center)
+                    * Affine2::from_angle(argument(0, 0.0).to_radians())
+                    * Affine2::from_translation(-center)
+            },
+            "skewX" => Affine2::from_cols_array(&[1.0, 0.0, argument(0, 0.0).to_radians().tan(), 1.0, 0.0, 0.0]),
+            "skewY" => Affine2::from_cols_array(&[1.0, argument(0, 0.0).to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => Affine2::IDENTITY,
+        };
+        transform = transform * next;
+    }
+    transform
+}
+
+/// Numbers in an attribute, separated by whitespace, commas or just the start of the next number.
+struct Numbers<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Numbers<'a> {
+    fn new(value: &'a str) -> Self {
+        Self {
+            bytes: value.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while matches!(self.bytes.get(self.position), Some(byte) if byte.is_ascii_whitespace() || *byte == b',') {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.position).copied()
+    }
+
+    /// Arc flags are single digits that may run straight into the next number.
+    fn flag(&mut self) -> Option<bool> {
+        let flag = match self.peek()? {
+            b'0' => false,
+            b'1' => true,
+            _ => return None,
+        };
+        self.position += 1;
+        Some(flag)
+    }
+
+    fn point(&mut self) -> Option<Vec2> {
+        Some(Vec2::new(self.next()?, self.next()?))
+    }
+}
+
+impl<'a> Iterator for Numbers<'a> {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.peek()?;
+        let start = self.position;
+        let mut end = start;
+        let digits = |end: &mut usize| {
+            while matches!(self.bytes.get(*end), Some(byte) if byte.is_ascii_digit()) {
+                *end += 1;
+            }
+        };
+
+        if matches!(self.bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        digits(&mut end);
+        if self.bytes.get(end) == Some(&b'.') {
+            end += 1;
+            digits(&mut end);
+        }
+        if matches!(self.bytes.get(end), Some(b'e' | b'E'))
+            && matches!(self.bytes.get(end + 1), Some(byte) if byte.is_ascii_digit() || *byte == b'-' || *byte == b'+')
+        {
+            end += 2;
+            digits(&mut end);
+        }
+
+        let number = std::str::from_utf8(&self.bytes[start..end]).ok()?.parse().ok()?;
+        self.position = end;
+        Some(number)
+    }
+}
+
+/// Flattens path data into subpaths. Parsing stops at the first error, keeping what came before it as the spec
+/// asks.
+fn flatten(data: &str, segments: u32) -> Vec<Subpath> {
+    let mut path = PathBuilder::default();
+    let mut numbers = Numbers::new(data);
+    let mut command = None;
+
+    while let Some(next) = numbers.peek() {
+        if next.is_ascii_alphabetic() {
+            command = Some(next);
+            numbers.position += 1;
+        }
+        let current = match command {
+            Some(current) => current,
+            None => break,
+        };
+        if path.command(current, &mut numbers, segments).is_none() {
+            break;
+        }
+        // coordinates repeated after a move continue as lines, and nothing can follow a close without a command
+        command = match current {
+            b'M' => Some(b'L'),
+            b'm' => Some(b'l'),
+            b'Z' | b'z' => None,
+            _ => command,
+        };
+    }
+    path.finish()
+}
+
+#[derive(Default)]
+struct PathBuilder {
+    subpaths: Vec<Subpath>,
+    current: Subpath,
+    pen: Vec2,
+    start: Vec2,
+    /// Second control point of the last cubic or the control point of the last quadratic, for smooth segments.
+    cubic_control: Option<Vec2>,
+    quad_control: Option<Vec2>,
+    /// Whether the current subpath has a Bezier curve in it.
+    curved: bool,
+}
+
+impl PathBuilder {
+    fn command(&mut self, command: u8, numbers: &mut Numbers, segments: u32) -> Option<()> {
+        let base = if command.is_ascii_lowercase() {
+            self.pen
+        } else {
+            Vec2::ZERO
+        };
+        let (mut cubic_control, mut quad_control) = (None, None);
+
+        match command.to_ascii_uppercase() {
+            b'M' => {
+                let point = numbers.point()? + base;
+                self.end_subpath();
+                self.pen = point;
+                self.begin();
+                self.start = point;
+            },
+            b'L' => {
+                let point = numbers.point()? + base;
+                self.line_to(point);
+            },
+            b'H' => {
+                let x = numbers.next()? + base.x;
+                self.line_to(Vec2::new(x, self.pen.y));
+            },
+            b'V' => {
+                let y = numbers.next()? + base.y;
+                self.line_to(Vec2::new(self.pen.x, y));
+            },
+            b'C' | b'S' => {
+                let first = match command.to_ascii_uppercase() {
+                    b'C' => numbers.point()? + base,
+                    _ => self.cubic_control.map_or(self.pen, |control| self.pen * 2.0 - control),
+                };
+                let second = numbers.point()? + base;
+                let end = numbers.point()? + base;
+                self.curve_to(first, second, end, segments);
+                cubic_control = Some(second);
+            },
+            b'Q' | b'T' => {
+                let control = match command.to_ascii_uppercase() {
+                    b'Q' => numbers.point()? + base,
+                    _ => self.quad_control.map_or(self.pen, |control| self.pen * 2.0 - control),
+                };
+                let end = numbers.point()? + base;
+                // a quadratic is the cubic with its control point two thirds of the way from either end
+                let start = self.pen;
+                self.curve_to(
+                    start.lerp(control, 2.0 / 3.0),
+                    end.lerp(control, 2.0 / 3.0),
+                    end,
+                    segments,
+                );
+                quad_control = Some(control);
+            },
+            b'A' => {
+                let radii = numbers.point()?;
+                let rotation = numbers.next()?;
+                let large_arc = numbers.flag()?;
+                let sweep = numbers.flag()?;
+                let end = numbers.point()? + base;
+                for point in arc(self.pen, radii, rotation, large_arc, sweep, end, segments) {
+                    self.line_to(point);
+                }
+            },
+            b'Z' => {
+                self.current.closed = true;
+                self.end_subpath();
+                self.pen = self.start;
+            },
+            _ => return None,
+        }
+
+        self.cubic_control = cubic_control;
+        self.quad_control = quad_control;
+        Some(())
+    }
+
+    /// Starts the current subpath at the pen unless it already has points.
+    fn begin(&mut self) {
+        // drawing on after a close starts a new subpath where the closed one began
+        if self.current.points.is_empty() {
+            self.current.points.push(self.pen);
+            self.current.curve.push(self.pen);
+        }
+    }
+
+    fn line_to(&mut self, point: Vec2) {
+        self.begin();
+        self.current.points.push(point);
+        let start = self.pen;
+        self.current
+            .curve
+            .extend([start.lerp(point, 1.0 / 3.0), start.lerp(point, 2.0 / 3.0), point]);
+        self.pen = point;
+    }
+
+    /// Cubic Bezier from the pen, kept as control points and flattened into `segments` segments.
+    fn curve_to(&mut self, first: Vec2, second: Vec2, end: Vec2, segments: u32) {
+        self.begin();
+        let start = self.pen;
+        for step in 1..=segments {
+            let t = step as f32 / segments as f32;
+            let [a, b, c] = [(start, first), (first, second), (second, end)].map(|(from, to)| from.lerp(to, t));
+            self.current.points.push(a.lerp(b, t).lerp(b.lerp(c, t), t));
+        }
+        self.current.curve.extend([first, second, end]);
+        self.curved = true;
+        self.pen = end;
+    }
+
+    fn end_subpath(&mut self) {
+        let mut subpath = std::mem::take(&mut self.current);
+        if subpath.closed && subpath.points.len() > 1 && subpath.points.first() == subpath.points.last() {
+            subpath.points.pop();
+        }
+        if !std::mem::take(&mut self.curved) {
+            subpath.curve.clear();
+        } else if let (true, Some(&first), Some(&last)) = (subpath.closed, subpath.curve.first(), subpath.curve.last())
+        {
+            if first != last {
+                subpath
+                    .curve
+                    .extend([last.lerp(first, 1.0 / 3.0), last.lerp(first, 2.0 / 3.0), first]);
+            }
+        }
+        if subpath.points.len() > 1 {
+            self.subpaths.push(subpath);
+        }
+    }
+
+    fn finish(mut self) -> Vec<Subpath> {
+        self.end_subpath();
+        self.subpaths
+    }
+}
+
+/// Points along an elliptical arc after `from`, by the endpoint to center conversion of the SVG implementation notes.
+fn arc(from: Vec2, radii: Vec2, rotation: f32, large_arc: bool, sweep: bool, to: Vec2, segments: u32) -> Vec<Vec2> {
+    if from == to {
+        return Vec::new();
+    }
+    let mut radii = radii.abs();
+    if radii.x == 0.0 || radii.y == 0.0 {
+        return vec![to];
+    }
+
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let half = (from - to) / 2.0;
+    let prime = Vec2::new(cos * half.x + sin * half.y, -sin * half.x + cos * half.y);
+    let lambda = (prime / radii).length_squared();
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+
+    let (rx2, ry2) = (radii.x * radii.x, radii.y * radii.y);
+    let (px2, py2) = (prime.x * prime.x, prime.y * prime.y);
+    let mut coefficient = ((rx2 * ry2 - rx2 * py2 - ry2 * px2) / (rx2 * py2 + ry2 * px2))
+        .max(0.0)
+        .sqrt();
+    if large_arc == sweep {
+        coefficient = -coefficient;
+    }
+    let center_prime = Vec2::new(radii.x * prime.y / radii.y, -radii.y * prime.x / radii.x) * coefficient;
+    let center = Vec2::new(
+        cos * center_prime.x - sin * center_prime.y,
+        sin * center_prime.x + cos * center_prime.y,
+    ) + (from + to) / 2.0;
+
+    let angle = |u: Vec2, v: Vec2| u.perp_dot(v).atan2(u.dot(v));
+    let start_vector = (prime - center_prime) / radii;
+    let end_vector = (-prime - center_prime) / radii;
+    let start_angle = angle(Vec2::X, start_vector);
+    let mut delta = angle(start_vector, end_vector);
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if sweep && delta < 0.0 {
+        delta += TAU;
+    }
+    let steps = segments * (delta.abs() / FRAC_PI_2).ceil().max(1.0) as u32;
+    (1..=steps)
+        .map(|step| {
+            if step == steps {
+                return to;
+            }
+            let theta = start_angle + delta * step as f32 / steps as f32;
+            let (sin_theta, cos_theta) = theta.sin_cos();
+            let local = Vec2::new(radii.x * cos_theta, radii.y * sin_theta);
+            center + Vec2::new(cos * local.x - sin * local.y, sin * local.x + cos * local.y)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_split_on_signs_and_decimal_points() {
+        assert_eq!(Numbers::new("1-2.5.5").collect::<Vec<_>>(), vec![1.0, -2.5, 0.5]);
+        assert_eq!(Numbers::new("1e2-3,4 .5e-1").collect::<Vec<_>>(), vec![
+            100.0, -3.0, 4.0, 0.05
+        ]);
+    }
+
+    #[test]
+    fn arc_flags_run_into_the_next_number() {
+        let packed = flatten("M0 0 A5 5 0 1110 0", 4);
+        let spaced = flatten("M0 0 A5 5 0 1 1 10 0", 4);
+        assert_eq!(packed.len(), 1);
+        assert_eq!(packed[0].points, spaced[0].points);
+        assert_eq!(packed[0].points.last(), Some(&Vec2::new(10.0, 0.0)));
+        for point in &packed[0].points {
+            assert!((point.distance(Vec2::new(5.0, 0.0)) - 5.0).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn relative_commands_after_close_start_at_the_subpath_start() {
+        let subpaths = flatten("M10 10 l5 0 l0 5 z l-5 0 l0 -5 z m1 1 h1 v1 z", 1);
+        assert_eq!(subpaths.len(), 3);
+        assert!(subpaths.iter().all(|subpath| subpath.closed));
+        assert_eq!(subpaths[1].points, vec![
+            Vec2::new(10.0, 10.0),
+            Vec2::new(5.0, 10.0),
+            Vec2::new(5.0, 5.0)
+        ]);
+        assert_eq!(subpaths[2].points[0], Vec2::new(11.0, 11.0));
+    }
+
+    #[test]
+    fn rect_rx_rounds_every_corner() {
+        let shapes = parse("<svg><rect x='0' y='0' width='10' height='6' rx='2'/></svg>", 4);
+        assert_eq!(shapes.len(), 1);
+        let subpath = &shapes[0].subpaths[0];
+        assert!(subpath.closed);
+        assert!(subpath.curve.is_empty());
+        for point in [[2.0, 0.0], [8.0, 0.0], [10.0, 2.0], [10.0, 4.0], [8.0, 6.0], [0.0, 2.0]] {
+            assert!(subpath.points.contains(&Vec2::from(point)), "missing {:?}", point);
+        }
+        for corner in [[0.0, 0.0], [10.0, 0.0], [10.0, 6.0], [0.0, 6.0]] {
+            assert!(
+                !subpath.points.contains(&Vec2::from(corner)),
+                "sharp corner {:?}",
+                corner
+            );
+        }
+    }
+
+    #[test]
+    fn bezier_subpaths_keep_their_control_points() {
+        let subpaths = flatten("M0 0 C1 1 2 1 3 0 L3 3", 8);
+        assert_eq!(subpaths[0].points.len(), 10);
+        assert_eq!(subpaths[0].curve[..4], [
+            Vec2::ZERO,
+            Vec2::new(1.0, 1.0),
+            Vec2::new(2.0, 1.0),
+            Vec2::new(3.0, 0.0)
+        ]);
+        assert_eq!(subpaths[0].curve.len(), 7);
+    }
+}